@@ -0,0 +1,75 @@
+//! Tabulated opset against the exact one it approximates.
+//!
+//! Run with `cargo +nightly bench`.
+
+#![feature(test)]
+
+extern crate test;
+
+use test::{black_box, Bencher};
+
+use fuzzy_systems::{fuzzy_opset, Membership, Opset, Tabulated};
+
+
+fuzzy_opset! {
+    /// Frank opset with base 2, expensive to evaluate.
+    pub Frank2 {
+         ~x   = 1.0 - x;
+        a | b = 1.0 - (1.0 + (2f64.powf(1.0 - a) - 1.0)
+            * (2f64.powf(1.0 - b) - 1.0)).log2();
+        a & b = (1.0 + (2f64.powf(a) - 1.0) * (2f64.powf(b) - 1.0)).log2();
+    }
+}
+
+type Frank2Fast = Tabulated<Frank2, 65>;
+
+fn inputs<S: Opset>() -> Vec<Membership<S>> {
+    (0..1024)
+        .map(|i| Membership::new((i * 37 % 1000) as f64 / 1000.0))
+        .collect()
+}
+
+/// Independent conjunctions of neighbouring values.
+fn pairs<S: Opset>(bench: &mut Bencher) {
+    let vals = inputs::<S>();
+    bench.iter(|| {
+        black_box(&vals)
+            .windows(2)
+            .map(|pair| (pair[0] & pair[1]).as_raw())
+            .sum::<f64>()
+    });
+}
+
+/// Chain of all operations, each depending on the previous one.
+fn chain<S: Opset>(bench: &mut Bencher) {
+    let vals = inputs::<S>();
+    bench.iter(|| {
+        black_box(&vals)
+            .windows(2)
+            .fold(Membership::new(0.0), |acc, pair| {
+                acc | (pair[0] & !pair[1])
+            })
+    });
+}
+
+#[bench]
+fn exact_pairs(bench: &mut Bencher) {
+    pairs::<Frank2>(bench);
+}
+
+#[bench]
+fn tabulated_pairs(bench: &mut Bencher) {
+    Frank2Fast::prepare();
+    pairs::<Frank2Fast>(bench);
+}
+
+#[bench]
+fn exact_chain(bench: &mut Bencher) {
+    chain::<Frank2>(bench);
+}
+
+#[bench]
+fn tabulated_chain(bench: &mut Bencher) {
+    Frank2Fast::prepare();
+    chain::<Frank2Fast>(bench);
+}
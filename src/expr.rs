@@ -1,7 +1,9 @@
 //! Fuzzy expressions that can be evaluated on-demand.
 //!
 //! Expressions are based on a statically-typed `Expr` trait implementors.
-//! No `Box` allocations are used, `Expr` is a truly zero-cost abstraction.
+//! Static nodes use no `Box` allocations, so they are a truly zero-cost
+//! abstraction. `ExprDyn` is the opt-in exception: it boxes its nodes, so
+//! that trees can be built at runtime (e.g. by `ExprDyn::parse`).
 //!
 //!
 //! ## Operators
@@ -29,6 +31,13 @@
 //! | alternation | a.or(b)  | a | b    |
 //! | conjunction | a.and(b) | a & b    |
 //!
+//! Any number of operands (arrays, tuples or `Vec`s of expressions) can be
+//! combined at once with `Expr::all` and `Expr::any`, yielding a single
+//! `ExprAll` or `ExprAny` node instead of a deeply nested type.
+//! Operands can be given importance weights with `a.weighted_and(w)`
+//! and `a.weighted_or(w)`, see `ExprWeighted`, and modified by linguistic
+//! hedges ("very", "somewhat", ...) with `a.hedge(h)`, see `ExprHedge`.
+//!
 //! Note: when passed generically, implentors of `Expr` cannot use
 //! ergonomic operator syntax but can still call methods explicitly.
 //! 
@@ -87,18 +96,74 @@
 //! Strings are built on-demand and no additional data is stored to build
 //! them, so this feature brings no overhead when not used.
 //!
+//! The same syntax can be parsed back into a dynamic expression,
+//! see `ExprDyn::parse`.
+//!
+//! Other styles (minimal parentheses, word or logic operators, tags along
+//! with their values) are available via `ExprFormatter`.
+//!
+//! Custom analyses of expression trees (counting leaves, collecting tags,
+//! evaluating with other rules, ...) can be written as a `Fold`.
+//! Dynamic expressions can be rewritten into equivalent smaller ones
+//! via `ExprDyn::simplify` and converted into normal forms via
+//! `ExprDyn::nnf`, `ExprDyn::dnf` and `ExprDyn::cnf`, or flattened
+//! into a `Program` evaluated without walking the tree via
+//! `ExprDyn::compile`.
+//!
 //! Note: when passed generically, implementors of `Expr` cannot be given
 //! names as only `ExprValue` can.
 //!
 //!
+//! ## Variables
+//!
+//! Value expressions hold a membership fixed at construction.
+//! Variable expressions are looked up in an input context instead,
+//! so a single tree can be evaluated for many inputs via
+//! `ExprWith::eval_with`:
+//!
+//! ```
+//! # use fuzzy_systems::{Expr, ExprWith, Hamacher1, Index, Opset};
+//! let a = Expr::<Hamacher1>::var(Index::<0>);
+//! let b = Expr::var(Index::<1>);
+//! let c = Expr::new(0.4);
+//! let d = (a | b) & !c;
+//! assert_eq!(d.to_string(), "((x0 | x1) & !0.4)");
+//!
+//! let input = (Hamacher1::member(0.1), Hamacher1::member(0.6));
+//! let value = d.eval_with(&input).as_raw();
+//! assert!((value - 0.384).abs() < 0.0001);
+//! ```
+//!
+//! Contexts are arrays, slices and `Vec`s (indexed by `usize`),
+//! tuples (indexed by `Index<N>`) or any type implementing `Context`.
+//! Expressions without variables can be evaluated with any context.
+//!
+//! Subexpressions used in several places can be shared, and evaluated
+//! only once, via `ExprShared`.
+//!
+//! Many inputs can be evaluated at once with `ExprBatch::eval_batch`,
+//! given variables' memberships as columns (see `Columns`).
+//!
+//!
 //! ## Expr vs other traits
 //! All standard implementors of `Expr` implement `Clone` (so `Expr<S> +
 //! Clone` is fine), `Debug` and `Display`.
 //! 
 //! `Expr` itself doesn't inherit either of the abovementioned traits to
-//! stay minimalistic and object-safe. Thanks to that, `Box<Expr<S>>`,
-//! providing `to_value()`, is possible. `Box<Expr<S> + Display>` (or
-//! `Debug`) and alike can provide other methods to the box.
+//! stay minimalistic and object-safe. Thanks to that, `Box<dyn Expr<S>>`,
+//! providing `to_value()`, is possible. `ExprDyn<S>` boxes any standard
+//! expression along with the traits above and, unlike the raw box,
+//! implements `Expr`, `Clone`, `Display`, `Debug` and the operators:
+//!
+//! ```
+//! # use fuzzy_systems::{Expr, ExprDyn, ExprRc, Hamacher1};
+//! let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+//! let b = Expr::new(0.6).with_tag("b");
+//! let c = ExprDyn::<Hamacher1>::new(a | b);
+//! let d: ExprRc<Hamacher1> = ExprRc::new(!c.clone());
+//! let e = c & d;
+//! assert_eq!(e.to_string(), "((a | b) & !(a | b))");
+//! ```
 //!
 //!
 //! ## Performance notes
@@ -106,6 +171,7 @@
 //! performance.
 
 mod value;
+mod var;
 
 mod tagged;
 pub mod tags;
@@ -113,8 +179,26 @@ pub mod tags;
 mod not;
 mod or;
 mod and;
+mod nary;
+mod operands;
+mod weighted;
+mod hedge;
+mod shared;
+mod batch;
 
 mod either;
+mod one_of;
+mod dynamic;
+mod opaque;
+mod parse;
+mod visit;
+mod term;
+mod simplify;
+mod normal;
+mod compile;
+mod explain;
+mod format;
+mod export;
 
 #[macro_use]
 mod macros;
@@ -123,14 +207,41 @@ mod macros;
 mod test;
 
 pub use self::value::ExprValue;
+pub use self::var::{Context, ExprVar, Index};
 pub use self::tagged::ExprTagged;
+pub use self::not::ExprNot;
+pub use self::or::ExprOr;
+pub use self::and::ExprAnd;
+pub use self::nary::{ExprAll, ExprAny};
+pub use self::operands::{
+    Operands, OperandsBatch, OperandsFormat, OperandsTerm, OperandsValue,
+    OperandsVisit, OperandsWith
+};
+pub use self::weighted::{ExprWeighted, Weight};
+pub use self::hedge::{ExprHedge, Hedge};
+pub use self::shared::{ExprShared, Memo};
+pub use self::batch::{Columns, ExprBatch};
 pub use self::either::ExprEither;
+pub use self::one_of::{
+    ExprOneOf3, ExprOneOf4, ExprOneOf5, ExprOneOf6, ExprOneOf7, ExprOneOf8
+};
+pub use self::dynamic::{
+    DynExpr, DynPtr, DynPtrFrom, ExprArc, ExprDyn, ExprPtr, ExprRc
+};
+pub use self::opaque::ExprOpaque;
+pub use self::parse::{ParseError, ParseErrorKind, Resolve};
+pub use self::format::{
+    ExprFormat, ExprFormatter, Formatted, Notation, Precedence
+};
+pub use self::visit::{ExprVisit, Fold, Modifier, Visitor};
+pub use self::term::{ExprTerm, Term};
+pub use self::normal::NormalForm;
+pub use self::compile::{Instr, Program};
+pub use self::explain::{Explanation, Step};
+pub use self::export::DotExporter;
 
-pub use self::macros::*;
-
-use self::not::*;
-use self::or::*;
-use self::and::*;
+#[doc(hidden)]
+pub use self::macros::__fuzzy_math;
 
 use crate::opset::Opset;
 use crate::value::{Membership, Raw};
@@ -140,10 +251,36 @@ use crate::value::{Membership, Raw};
 // but can implement them as aliases in any of the implementing structs.
 
 /// Evaluable fuzzy expression.
+///
+/// Standard expressions are also evaluable with the uninhabited context
+/// `!`, which provides any variable. `ExprWith<S, !>` thus bounds them
+/// regardless of their variables, closed or not. Other implementors
+/// combine with them via `and`, `or` and `fuzzy_math!`.
 pub trait Expr<S: Opset> {
     /// Expression's value.
     fn to_value(&self) -> Membership<S>;
 
+    /// Expression's value, along with values of all its subexpressions.
+    ///
+    /// ```
+    /// # use fuzzy_systems::{Expr, Hamacher1};
+    /// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+    /// let b = Expr::new(0.6).with_tag("b");
+    /// let d = a | !b;
+    /// assert_eq!(format!("{:.2}", d.explain()), "\
+    /// or = 0.46
+    ///   a = 0.10
+    ///   not = 0.40
+    ///     b = 0.60
+    /// ");
+    /// ```
+    fn explain(&self) -> Explanation<S>
+    where
+        Self: ExprVisit<S, !> + Sized
+    {
+        Explanation::eval(self)
+    }
+
     /// "Not" expression.
     fn not(self) -> ExprNot<S, Self>
     where
@@ -170,6 +307,36 @@ pub trait Expr<S: Opset> {
         ExprOr::new(self, rhs)
     }
 
+    /// Operand of a conjunction weighted by its importance.
+    ///
+    /// Panics if the weight is not between 0.0 and 1.0 inclusive.
+    fn weighted_and(self, weight: Raw) -> ExprWeighted<S, Self>
+    where
+        Self: Sized
+    {
+        ExprWeighted::new(self, Weight::And(Membership::new(weight)))
+    }
+
+    /// Operand of an alternative weighted by its importance.
+    ///
+    /// Panics if the weight is not between 0.0 and 1.0 inclusive.
+    fn weighted_or(self, weight: Raw) -> ExprWeighted<S, Self>
+    where
+        Self: Sized
+    {
+        ExprWeighted::new(self, Weight::Or(Membership::new(weight)))
+    }
+
+    /// Expression modified by a linguistic hedge.
+    fn hedge(self, hedge: Hedge) -> ExprHedge<S, Self>
+    where
+        Self: Sized
+    {
+        ExprHedge::new(self, hedge)
+    }
+
+    /// Left branch of a runtime choice.
+    #[allow(clippy::wrong_self_convention)]
     fn as_left<R>(self) -> ExprEither<S, Self, R>
     where
         Self: Sized,
@@ -178,6 +345,8 @@ pub trait Expr<S: Opset> {
         ExprEither::left(self)
     }
 
+    /// Right branch of a runtime choice.
+    #[allow(clippy::wrong_self_convention)]
     fn as_right<L>(self) -> ExprEither<S, L, Self>
     where
         Self: Sized,
@@ -187,7 +356,17 @@ pub trait Expr<S: Opset> {
     }
 }
 
-impl<S: Opset> Expr<S> {
+/// Fuzzy expression evaluable against an input context.
+///
+/// Implemented by all standard expressions for any context they can
+/// be evaluated with: ones without `ExprVar` leaves for any context,
+/// ones with variables for contexts providing all of them.
+pub trait ExprWith<S: Opset, C: ?Sized> {
+    /// Expression's value for the given input.
+    fn eval_with(&self, ctx: &C) -> Membership<S>;
+}
+
+impl<S: Opset> dyn Expr<S> {
     pub fn new(raw: Raw) -> ExprValue<S> {
         ExprValue::new(raw)
     }
@@ -199,4 +378,22 @@ impl<S: Opset> Expr<S> {
     pub fn from_membership(membership: Membership<S>) -> ExprValue<S> {
         ExprValue::from_membership(membership)
     }
+
+    pub fn var<I>(idx: I) -> ExprVar<S, I> {
+        ExprVar::new(idx)
+    }
+
+    pub fn all<L>(ops: L) -> ExprAll<S, L>
+    where
+        L: OperandsWith<S, !>
+    {
+        ExprAll::new(ops)
+    }
+
+    pub fn any<L>(ops: L) -> ExprAny<S, L>
+    where
+        L: OperandsWith<S, !>
+    {
+        ExprAny::new(ops)
+    }
 }
@@ -1,7 +1,11 @@
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::marker::PhantomData;
 
-use super::Expr;
+use super::{
+    Expr, ExprBatch, ExprFormat, ExprFormatter, ExprTerm, ExprVisit, ExprWith,
+    Precedence, Term, Visitor
+};
+use super::batch::scratch;
 
 use crate::opset::Opset;
 use crate::value::Membership;
@@ -53,6 +57,46 @@ impl<S, L: Display, R: Display> Display for ExprAnd<S, L, R> {
     }
 }
 
+impl<S, L, R> ExprFormat for ExprAnd<S, L, R>
+where
+    L: ExprFormat,
+    R: ExprFormat
+{
+    fn precedence(&self) -> Precedence {
+        Precedence::And
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        fmt.fmt_and(f, &self.lhs, &self.rhs)
+    }
+}
+
+impl<S, L, R, C> ExprVisit<S, C> for ExprAnd<S, L, R>
+where
+    S: Opset,
+    L: ExprVisit<S, C>,
+    R: ExprVisit<S, C>,
+    C: ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        self.lhs.visit(ctx, visitor);
+        self.rhs.visit(ctx, visitor);
+        visitor.and();
+    }
+}
+
+impl<S, L, R, C> ExprTerm<S, C> for ExprAnd<S, L, R>
+where
+    S: Opset,
+    L: ExprTerm<S, C>,
+    R: ExprTerm<S, C>,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::And(Box::new(self.lhs.to_term()), Box::new(self.rhs.to_term()))
+    }
+}
+
 impl<S, L, R> Expr<S> for ExprAnd<S, L, R>
 where
     S: Opset,
@@ -67,6 +111,37 @@ where
     }
 }
 
+impl<S, L, R, C> ExprWith<S, C> for ExprAnd<S, L, R>
+where
+    S: Opset,
+    L: ExprWith<S, C>,
+    R: ExprWith<S, C>,
+    C: ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        let lhs = self.lhs.eval_with(ctx);
+        let rhs = self.rhs.eval_with(ctx);
+        lhs & rhs
+    }
+}
+
+impl<S, L, R, B> ExprBatch<S, B> for ExprAnd<S, L, R>
+where
+    S: Opset,
+    L: ExprBatch<S, B>,
+    R: ExprBatch<S, B>,
+    B: ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        let mut rhs = scratch(out.len());
+        self.lhs.eval_batch(cols, out);
+        self.rhs.eval_batch(cols, &mut rhs);
+        S::and_slice(out, &rhs);
+    }
+}
+
 impl_fuzzy_expr_ops! {
     ExprAnd<S, L, R>
 }
@@ -0,0 +1,164 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use super::Index;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Input columns providing memberships of variables for many rows.
+///
+/// The struct-of-arrays counterpart of `Context`: `usize`-indexed
+/// for arrays, slices and `Vec`s of columns, `Index<N>`-indexed for
+/// tuples of columns, key-indexed for maps (which panic on missing
+/// keys) or any user type. Columns are anything viewable as a slice
+/// of memberships.
+pub trait Columns<S: Opset, I> {
+    /// Memberships of a variable, one per row.
+    fn column(&self, idx: &I) -> &[Membership<S>];
+}
+
+impl<S, V> Columns<S, usize> for [V]
+where
+    S: Opset,
+    V: AsRef<[Membership<S>]>
+{
+    #[inline]
+    fn column(&self, idx: &usize) -> &[Membership<S>] {
+        self[*idx].as_ref()
+    }
+}
+
+impl<S, V, const N: usize> Columns<S, usize> for [V; N]
+where
+    S: Opset,
+    V: AsRef<[Membership<S>]>
+{
+    #[inline]
+    fn column(&self, idx: &usize) -> &[Membership<S>] {
+        self[*idx].as_ref()
+    }
+}
+
+impl<S, V> Columns<S, usize> for Vec<V>
+where
+    S: Opset,
+    V: AsRef<[Membership<S>]>
+{
+    #[inline]
+    fn column(&self, idx: &usize) -> &[Membership<S>] {
+        self[*idx].as_ref()
+    }
+}
+
+impl<S, K, V, I, H> Columns<S, I> for HashMap<K, V, H>
+where
+    S: Opset,
+    K: Borrow<I> + Hash + Eq,
+    V: AsRef<[Membership<S>]>,
+    I: Hash + Eq,
+    H: BuildHasher
+{
+    #[inline]
+    fn column(&self, idx: &I) -> &[Membership<S>] {
+        self[idx].as_ref()
+    }
+}
+
+impl<S, K, V, I> Columns<S, I> for BTreeMap<K, V>
+where
+    S: Opset,
+    K: Borrow<I> + Ord,
+    V: AsRef<[Membership<S>]>,
+    I: Ord
+{
+    #[inline]
+    fn column(&self, idx: &I) -> &[Membership<S>] {
+        self[idx].as_ref()
+    }
+}
+
+impl<S: Opset, I, B: Columns<S, I> + ?Sized> Columns<S, I> for &B {
+    #[inline]
+    fn column(&self, idx: &I) -> &[Membership<S>] {
+        (**self).column(idx)
+    }
+}
+
+/// Implement `Columns` for tuples, indexed by `Index<N>`.
+macro_rules! impl_tuple_columns {
+    ($( ($($n:tt: $v:ident),+) )*) => {
+        $( impl_tuple_columns!(@elem [$($v),+] $($n $v),+); )*
+    };
+
+    (@elem [$($all:ident),+] $n:tt $v:ident $(, $rn:tt $rv:ident)*) => {
+        impl<S, $($all),+> Columns<S, Index<$n>> for ($($all,)+)
+        where
+            S: Opset,
+            $($all: AsRef<[Membership<S>]>),+
+        {
+            #[inline]
+            fn column(&self, _idx: &Index<$n>) -> &[Membership<S>] {
+                self.$n.as_ref()
+            }
+        }
+
+        impl_tuple_columns!(@elem [$($all),+] $($rn $rv),*);
+    };
+
+    (@elem [$($all:ident),+]) => {};
+}
+
+impl_tuple_columns! {
+    (0: V0)
+    (0: V0, 1: V1)
+    (0: V0, 1: V1, 2: V2)
+    (0: V0, 1: V1, 2: V2, 3: V3)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4, 5: V5)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4, 5: V5, 6: V6)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4, 5: V5, 6: V6, 7: V7)
+}
+
+
+/// Fuzzy expression evaluable over a batch of inputs at once.
+///
+/// Implemented by all standard expressions, other than dynamic ones,
+/// for columns providing all their variables. Each node is evaluated
+/// for all rows before its parent, using the slice-wise operations
+/// of the opset (`Opset::and_slice`, ...):
+///
+/// ```
+/// # use fuzzy_systems::{Expr, ExprBatch, ExprWith, Hamacher1, Membership};
+/// let rule = (Expr::<Hamacher1>::var(0) | Expr::var(1)) & !Expr::new(0.4);
+///
+/// let a: Vec<Membership<_>> = (0..1000)
+///     .map(|i| Membership::new(i as f64 / 1000.0))
+///     .collect();
+/// let b: Vec<Membership<_>> = a.iter().rev().copied().collect();
+///
+/// let out = rule.to_batch(&[&a, &b], a.len());
+/// for i in 0..a.len() {
+///     assert_eq!(out[i], rule.eval_with(&[a[i], b[i]]));
+/// }
+/// ```
+pub trait ExprBatch<S: Opset, B: ?Sized> {
+    /// Writes the expression's value for each of the first `out.len()`
+    /// rows into `out`. Panics if a column is shorter.
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]);
+
+    /// Expression's value for each of the first `rows` rows.
+    /// Panics if a column is shorter.
+    fn to_batch(&self, cols: &B, rows: usize) -> Vec<Membership<S>> {
+        let mut out = scratch(rows);
+        self.eval_batch(cols, &mut out);
+        out
+    }
+}
+
+/// Buffer for values of `len` rows.
+pub(crate) fn scratch<S: Opset>(len: usize) -> Vec<Membership<S>> {
+    vec![Membership::new(0.0); len]
+}
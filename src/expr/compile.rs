@@ -0,0 +1,293 @@
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Stack depth of programs evaluated without allocation.
+const INLINE_DEPTH: usize = 16;
+
+
+/// Instruction of a `Program`, operating on a stack of memberships.
+pub enum Instr<S: Opset, C: ?Sized> {
+    /// Pushes constant.
+    Const(Membership<S>),
+    /// Pushes value of an opaque leaf, evaluated with the context.
+    Load(ExprDyn<S, C>),
+    /// Negates the top value.
+    Not,
+    /// Replaces the two top values with their conjunction.
+    And,
+    /// Replaces the two top values with their alternative.
+    Or
+}
+
+impl<S: Opset, C: ?Sized> Clone for Instr<S, C> {
+    fn clone(&self) -> Self {
+        match self {
+            Instr::Const(val) => Instr::Const(*val),
+            Instr::Load(expr) => Instr::Load(expr.clone()),
+            Instr::Not => Instr::Not,
+            Instr::And => Instr::And,
+            Instr::Or => Instr::Or
+        }
+    }
+}
+
+impl<S: Opset, C: ?Sized> Debug for Instr<S, C> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Instr::Const(val) => write!(f, "Const({:?})", val),
+            Instr::Load(expr) => write!(f, "Load({:?})", expr),
+            Instr::Not => write!(f, "Not"),
+            Instr::And => write!(f, "And"),
+            Instr::Or => write!(f, "Or")
+        }
+    }
+}
+
+impl<S: Opset, C: ?Sized> Display for Instr<S, C> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Instr::Const(val) => write!(f, "const {}", val),
+            Instr::Load(expr) => write!(f, "load {}", expr),
+            Instr::Not => write!(f, "not"),
+            Instr::And => write!(f, "and"),
+            Instr::Or => write!(f, "or")
+        }
+    }
+}
+
+
+/// Expression flattened into a linear list of instructions.
+///
+/// Evaluated in a single loop over the list, without walking the tree
+/// or calling through pointers other than for opaque leaves (variables,
+/// weighted operands, ...). Subtrees without variables are evaluated
+/// once, on compilation. See `ExprDyn::compile`.
+pub struct Program<S: Opset, C: ?Sized> {
+    instrs: Vec<Instr<S, C>>,
+    depth: usize
+}
+
+impl<S: Opset, C: ?Sized> Program<S, C> {
+    /// Compiles term.
+    pub fn new(term: &Term<S, C>) -> Self {
+        let mut instrs = Vec::with_capacity(term.size());
+        Self::emit(term, &mut instrs);
+
+        let mut depth = 0;
+        let mut height = 0;
+        for instr in &instrs {
+            match instr {
+                Instr::Const(_) | Instr::Load(_) => height += 1,
+                Instr::Not => {},
+                Instr::And | Instr::Or => height -= 1
+            }
+            depth = depth.max(height);
+        }
+
+        Self {
+            instrs,
+            depth
+        }
+    }
+
+    /// Instructions, in order of execution.
+    pub fn instrs(&self) -> &[Instr<S, C>] {
+        &self.instrs
+    }
+
+    /// Stack size needed for evaluation.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Appends instructions of the term, folding constants.
+    fn emit(term: &Term<S, C>, instrs: &mut Vec<Instr<S, C>>) {
+        match term {
+            Term::Const(val) => instrs.push(Instr::Const(*val)),
+            Term::Leaf(expr) => {
+                let instr = match Constant.fold_impl(expr, None) {
+                    Some(val) => Instr::Const(val),
+                    None => Instr::Load(expr.clone())
+                };
+                instrs.push(instr);
+            },
+            Term::Not(val) => {
+                Self::emit(val, instrs);
+                match instrs.last_mut() {
+                    Some(Instr::Const(val)) => *val = S::not(*val),
+                    _ => instrs.push(Instr::Not)
+                }
+            },
+            Term::And(lhs, rhs) => {
+                Self::emit(lhs, instrs);
+                Self::emit(rhs, instrs);
+                Self::binary(instrs, Instr::And, S::and);
+            },
+            Term::Or(lhs, rhs) => {
+                Self::emit(lhs, instrs);
+                Self::emit(rhs, instrs);
+                Self::binary(instrs, Instr::Or, S::or);
+            }
+        }
+    }
+
+    /// Appends binary instruction, folded if both operands are constant.
+    fn binary<F>(instrs: &mut Vec<Instr<S, C>>, instr: Instr<S, C>, op: F)
+    where
+        F: Fn(Membership<S>, Membership<S>) -> Membership<S>
+    {
+        if let [.., Instr::Const(lhs), Instr::Const(rhs)] = instrs[..] {
+            instrs.truncate(instrs.len() - 2);
+            instrs.push(Instr::Const(op(lhs, rhs)));
+        } else {
+            instrs.push(instr);
+        }
+    }
+
+    /// Runs the instructions on the stack, which must be deep enough.
+    #[inline]
+    fn run(&self, ctx: &C, stack: &mut [Membership<S>]) -> Membership<S> {
+        let mut top = 0;
+        for instr in &self.instrs {
+            match instr {
+                Instr::Const(val) => {
+                    stack[top] = *val;
+                    top += 1;
+                },
+                Instr::Load(expr) => {
+                    stack[top] = expr.eval_with(ctx);
+                    top += 1;
+                },
+                Instr::Not => {
+                    stack[top - 1] = S::not(stack[top - 1]);
+                },
+                Instr::And => {
+                    top -= 1;
+                    stack[top - 1] = S::and(stack[top - 1], stack[top]);
+                },
+                Instr::Or => {
+                    top -= 1;
+                    stack[top - 1] = S::or(stack[top - 1], stack[top]);
+                }
+            }
+        }
+        stack[0]
+    }
+}
+
+impl<S: Opset, C: ?Sized> Clone for Program<S, C> {
+    fn clone(&self) -> Self {
+        Self {
+            instrs: self.instrs.clone(),
+            depth: self.depth
+        }
+    }
+}
+
+impl<S: Opset, C: ?Sized> Debug for Program<S, C> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Program({:?})", self.instrs)
+    }
+}
+
+impl<S: Opset, C: ?Sized> Display for Program<S, C> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for (i, instr) in self.instrs.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", instr)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Opset, C: ?Sized> ExprWith<S, C> for Program<S, C> {
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        if self.depth <= INLINE_DEPTH {
+            let mut stack = [Membership::new(0.0); INLINE_DEPTH];
+            self.run(ctx, &mut stack)
+        } else {
+            let mut stack = vec![Membership::new(0.0); self.depth];
+            self.run(ctx, &mut stack)
+        }
+    }
+}
+
+impl<S: Opset> ExprWith<S, !> for Program<S, ()> {
+    fn eval_with(&self, ctx: &!) -> Membership<S> {
+        *ctx
+    }
+}
+
+impl<S: Opset> Expr<S> for Program<S, ()> {
+    #[inline]
+    fn to_value(&self) -> Membership<S> {
+        self.eval_with(&())
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> ExprPtr<S, C, P> {
+    /// Flattens into a `Program` evaluating to the same value.
+    ///
+    /// Meant for expressions built or parsed at runtime and evaluated
+    /// many times, avoiding a pointer call per node:
+    ///
+    /// ```
+    /// # use fuzzy_systems::{Expr, ExprDyn, ExprWith, Hamacher1, Membership};
+    /// let rule = ExprDyn::<Hamacher1, [Membership<Hamacher1>]>::new(
+    ///     (Expr::var(0) | Expr::var(1))
+    ///         & !(Expr::new(1.0).with_tag("c") & Expr::new(0.4))
+    /// );
+    /// let program = rule.compile();
+    /// assert_eq!(program.to_string(), "load 0; load 1; or; const 0.6; and");
+    ///
+    /// let input = [Membership::new(0.1), Membership::new(0.6)];
+    /// assert_eq!(program.eval_with(&input[..]), rule.eval_with(&input[..]));
+    /// ```
+    pub fn compile(&self) -> Program<S, C> {
+        Program::new(&self.to_term())
+    }
+}
+
+
+/// Value of expressions without variables.
+struct Constant;
+
+impl<S: Opset> Fold<S> for Constant {
+    type Output = Option<Membership<S>>;
+
+    fn value(&mut self, val: Membership<S>) -> Self::Output {
+        Some(val)
+    }
+
+    fn var(&mut self, _idx: &dyn Display, _val: Option<Membership<S>>)
+        -> Self::Output {
+
+        None
+    }
+
+    fn not(&mut self, val: Self::Output) -> Self::Output {
+        val.map(S::not)
+    }
+
+    fn and(&mut self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        Some(S::and(lhs?, rhs?))
+    }
+
+    fn or(&mut self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        Some(S::or(lhs?, rhs?))
+    }
+
+    fn apply(&mut self, op: &dyn Modifier<S>, val: Self::Output)
+        -> Self::Output {
+
+        val.map(|val| op.apply(val))
+    }
+}
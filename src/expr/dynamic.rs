@@ -0,0 +1,338 @@
+use std::any::Any;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::ops::{Not, BitAnd, BitOr};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Object-safe fuzzy expression, as held by `ExprPtr`.
+///
+/// Implemented by every expression evaluable with context `C`
+/// which is also `ExprFormat`, `ExprVisit`, `ExprTerm`, `Display`, `Debug`
+/// and `Clone`. Other expressions are wrapped by `ExprPtr::opaque`.
+pub trait DynExpr<S: Opset, C: ?Sized>:
+    ExprWith<S, C> + ExprFormat + ExprVisit<S, C> + ExprTerm<S, C> + Display
+        + Debug
+{
+    /// Clones into a new box.
+    fn clone_box(&self) -> Box<dyn DynExpr<S, C>>;
+
+    /// Upcasts for downcasting, see `ExprTerm::same_leaf`.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<S, C, E> DynExpr<S, C> for E
+where
+    S: Opset,
+    C: ?Sized,
+    E: ExprWith<S, C> + ExprFormat + ExprVisit<S, C> + ExprTerm<S, C>
+        + Display + Debug + Clone + 'static
+{
+    fn clone_box(&self) -> Box<dyn DynExpr<S, C>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl<S: Opset, C: ?Sized> Clone for Box<dyn DynExpr<S, C>> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+
+/// Pointer holding a `DynExpr` in `ExprPtr`.
+pub trait DynPtr<S: Opset, C: ?Sized>: Clone {
+    /// Name of `ExprPtr` holding this pointer, as shown by `Debug`.
+    const NAME: &'static str = "ExprPtr";
+
+    /// Pointed expression.
+    fn node(&self) -> &dyn DynExpr<S, C>;
+}
+
+/// Pointer able to hold expression `E`.
+pub trait DynPtrFrom<S: Opset, C: ?Sized, E>: DynPtr<S, C> {
+    /// Moves expression behind the pointer.
+    fn wrap(expr: E) -> Self;
+}
+
+impl<S: Opset, C: ?Sized> DynPtr<S, C> for Box<dyn DynExpr<S, C>> {
+    const NAME: &'static str = "ExprDyn";
+
+    #[inline]
+    fn node(&self) -> &dyn DynExpr<S, C> {
+        &**self
+    }
+}
+
+impl<S, C, E> DynPtrFrom<S, C, E> for Box<dyn DynExpr<S, C>>
+where
+    S: Opset,
+    C: ?Sized,
+    E: DynExpr<S, C> + 'static
+{
+    fn wrap(expr: E) -> Self {
+        Box::new(expr)
+    }
+}
+
+impl<S: Opset, C: ?Sized> DynPtr<S, C> for Rc<dyn DynExpr<S, C>> {
+    const NAME: &'static str = "ExprRc";
+
+    #[inline]
+    fn node(&self) -> &dyn DynExpr<S, C> {
+        &**self
+    }
+}
+
+impl<S, C, E> DynPtrFrom<S, C, E> for Rc<dyn DynExpr<S, C>>
+where
+    S: Opset,
+    C: ?Sized,
+    E: DynExpr<S, C> + 'static
+{
+    fn wrap(expr: E) -> Self {
+        Rc::new(expr)
+    }
+}
+
+impl<S: Opset, C: ?Sized> DynPtr<S, C> for Arc<dyn DynExpr<S, C> + Send + Sync> {
+    const NAME: &'static str = "ExprArc";
+
+    #[inline]
+    fn node(&self) -> &dyn DynExpr<S, C> {
+        &**self
+    }
+}
+
+impl<S, C, E> DynPtrFrom<S, C, E> for Arc<dyn DynExpr<S, C> + Send + Sync>
+where
+    S: Opset,
+    C: ?Sized,
+    E: DynExpr<S, C> + Send + Sync + 'static
+{
+    fn wrap(expr: E) -> Self {
+        Arc::new(expr)
+    }
+}
+
+
+/// Dynamic fuzzy expression, type-erased behind pointer `P`.
+///
+/// Used via its aliases: `ExprDyn` (boxed, cloning clones the whole tree),
+/// `ExprRc` and `ExprArc` (shared, cloning shares the tree).
+/// `C` is the context the expression is evaluated with, `()` for
+/// expressions without variables.
+pub struct ExprPtr<S, C, P>
+where
+    S: Opset,
+    C: ?Sized
+{
+    ptr: P,
+    phantom: PhantomData<S>,
+    context: PhantomData<fn(&C)>
+}
+
+/// Dynamic fuzzy expression, boxed.
+///
+/// Can wrap any standard expression, so trees built from runtime data
+/// have a single type. Combining dynamic expressions with operators
+/// yields a dynamic expression again:
+///
+/// ```
+/// # use fuzzy_systems::{Expr, ExprDyn, Hamacher1};
+/// let conditions = vec![0.1, 0.6, 0.4];
+///
+/// let mut d = ExprDyn::<Hamacher1>::new(Expr::new(0.0));
+/// for (i, &raw) in conditions.iter().enumerate() {
+///     let leaf = ExprDyn::new(Expr::new(raw));
+///     d = if i % 2 == 0 { d | leaf } else { d & !leaf };
+/// }
+/// assert_eq!(d.to_string(), "(((0 | 0.1) & !0.6) | 0.4)");
+/// assert!((d.to_value().as_raw() - 0.424).abs() < 0.0001);
+/// ```
+pub type ExprDyn<S, C = ()> = ExprPtr<S, C, Box<dyn DynExpr<S, C>>>;
+
+/// Dynamic fuzzy expression, shared via `Rc`.
+pub type ExprRc<S, C = ()> = ExprPtr<S, C, Rc<dyn DynExpr<S, C>>>;
+
+/// Dynamic fuzzy expression, shared via `Arc`.
+pub type ExprArc<S, C = ()> =
+    ExprPtr<S, C, Arc<dyn DynExpr<S, C> + Send + Sync>>;
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> ExprPtr<S, C, P> {
+    /// Creates from any expression the pointer can hold.
+    pub fn new<E>(expr: E) -> Self
+    where
+        P: DynPtrFrom<S, C, E>
+    {
+        Self::from_ptr(P::wrap(expr))
+    }
+
+    /// Creates from expression implementing only `ExprWith`, e.g. from
+    /// other crates, as an opaque leaf named `name`, see `ExprOpaque`:
+    ///
+    /// ```
+    /// # use fuzzy_systems::{ExprDyn, ExprWith, Hamacher1, Membership};
+    /// #[derive(Clone)]
+    /// struct Warm;
+    ///
+    /// impl ExprWith<Hamacher1, f64> for Warm {
+    ///     fn eval_with(&self, celsius: &f64) -> Membership<Hamacher1> {
+    ///         Membership::new(((celsius - 15.0) / 10.0).clamp(0.0, 1.0))
+    ///     }
+    /// }
+    ///
+    /// let warm = ExprDyn::<Hamacher1, f64>::opaque("warm", Warm);
+    /// let rule = warm.clone() & !warm;
+    /// assert_eq!(rule.to_string(), "(warm & !warm)");
+    /// assert_eq!(rule.eval_with(&20.0).as_raw(), 0.25);
+    /// ```
+    pub fn opaque<E, N>(name: N, expr: E) -> Self
+    where
+        E: ExprWith<S, C>,
+        P: DynPtrFrom<S, C, ExprOpaque<S, C, E, N>>
+    {
+        Self::new(ExprOpaque::new(name, expr))
+    }
+
+    /// Creates from expression implementing only `Expr`, as `opaque`.
+    pub fn opaque_value<E, N>(name: N, expr: E) -> Self
+    where
+        E: Expr<S>,
+        P: DynPtrFrom<S, C, ExprOpaque<S, C, E, N>>
+    {
+        Self::new(ExprOpaque::from_value(name, expr))
+    }
+
+    /// Creates from pointer.
+    pub fn from_ptr(ptr: P) -> Self {
+        Self {
+            ptr,
+            phantom: PhantomData,
+            context: PhantomData
+        }
+    }
+
+    /// Wrapped expression.
+    pub fn node(&self) -> &dyn DynExpr<S, C> {
+        self.ptr.node()
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> Clone for ExprPtr<S, C, P> {
+    fn clone(&self) -> Self {
+        Self::from_ptr(self.ptr.clone())
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> Debug for ExprPtr<S, C, P> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}({:?})", P::NAME, self.node())
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> Display for ExprPtr<S, C, P> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.node())
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> ExprFormat for ExprPtr<S, C, P> {
+    fn precedence(&self) -> Precedence {
+        self.node().precedence()
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        self.node().fmt_with(fmt, f)
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> ExprVisit<S, C> for ExprPtr<S, C, P> {
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        self.node().visit(ctx, visitor);
+    }
+}
+
+impl<S: Opset, P: DynPtr<S, ()>> ExprVisit<S, !> for ExprPtr<S, (), P> {
+    fn visit(&self, _ctx: Option<&!>, visitor: &mut dyn Visitor<S>) {
+        self.node().visit(Some(&()), visitor);
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> ExprTerm<S, C> for ExprPtr<S, C, P> {
+    fn to_term(&self) -> Term<S, C> {
+        self.node().to_term()
+    }
+
+    fn same_leaf(&self, other: &dyn Any) -> bool {
+        self.node().same_leaf(other)
+    }
+}
+
+impl<S: Opset, C: ?Sized, P: DynPtr<S, C>> ExprWith<S, C> for ExprPtr<S, C, P> {
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        self.node().eval_with(ctx)
+    }
+}
+
+impl<S: Opset, P: DynPtr<S, ()>> ExprWith<S, !> for ExprPtr<S, (), P> {
+    fn eval_with(&self, ctx: &!) -> Membership<S> {
+        *ctx
+    }
+}
+
+impl<S: Opset, P: DynPtr<S, ()>> Expr<S> for ExprPtr<S, (), P> {
+    #[inline]
+    fn to_value(&self) -> Membership<S> {
+        self.node().eval_with(&())
+    }
+}
+
+impl<S, C, P> Not for ExprPtr<S, C, P>
+where
+    S: Opset,
+    C: ?Sized,
+    P: DynPtrFrom<S, C, ExprNot<S, Self>>
+{
+    type Output = Self;
+    fn not(self) -> Self {
+        Self::new(ExprNot::new(self))
+    }
+}
+
+impl<S, C, P, R> BitAnd<R> for ExprPtr<S, C, P>
+where
+    S: Opset,
+    C: ?Sized,
+    P: DynPtrFrom<S, C, ExprAnd<S, Self, R>>,
+    R: ExprWith<S, C>
+{
+    type Output = Self;
+    fn bitand(self, rhs: R) -> Self {
+        Self::new(ExprAnd::new(self, rhs))
+    }
+}
+
+impl<S, C, P, R> BitOr<R> for ExprPtr<S, C, P>
+where
+    S: Opset,
+    C: ?Sized,
+    P: DynPtrFrom<S, C, ExprOr<S, Self, R>>,
+    R: ExprWith<S, C>
+{
+    type Output = Self;
+    fn bitor(self, rhs: R) -> Self {
+        Self::new(ExprOr::new(self, rhs))
+    }
+}
@@ -1,7 +1,10 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::marker::PhantomData;
-use std::string::ToString;
 
-use super::Expr;
+use super::{
+    Expr, ExprBatch, ExprFormat, ExprFormatter, ExprTerm, ExprVisit, ExprWith,
+    Precedence, Term, Visitor
+};
 
 use crate::opset::Opset;
 use crate::value::Membership;
@@ -62,15 +65,69 @@ where
     }
 }
 
-impl<S, L, R> ToString for ExprEither<S, L, R>
+impl<S, L, R> Display for ExprEither<S, L, R>
 where
-    L: ToString,
-    R: ToString
+    L: Display,
+    R: Display
 {
-    fn to_string(&self) -> String {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
         match self {
-            ExprEither::Left(e) => e.to_string(),
-            ExprEither::Right(e) => e.to_string(),
+            ExprEither::Left(e) => write!(f, "{}", e),
+            ExprEither::Right(e) => write!(f, "{}", e),
+            ExprEither::Never(..) => panic!("Invalid state!")
+        }
+    }
+}
+
+impl<S, L, R> ExprFormat for ExprEither<S, L, R>
+where
+    L: ExprFormat,
+    R: ExprFormat
+{
+    fn precedence(&self) -> Precedence {
+        match self {
+            ExprEither::Left(e) => e.precedence(),
+            ExprEither::Right(e) => e.precedence(),
+            ExprEither::Never(..) => panic!("Invalid state!")
+        }
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        match self {
+            ExprEither::Left(e) => e.fmt_with(fmt, f),
+            ExprEither::Right(e) => e.fmt_with(fmt, f),
+            ExprEither::Never(..) => panic!("Invalid state!")
+        }
+    }
+}
+
+impl<S, L, R, C> ExprVisit<S, C> for ExprEither<S, L, R>
+where
+    S: Opset,
+    L: ExprVisit<S, C>,
+    R: ExprVisit<S, C>,
+    C: ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        match self {
+            ExprEither::Left(e) => e.visit(ctx, visitor),
+            ExprEither::Right(e) => e.visit(ctx, visitor),
+            ExprEither::Never(..) => panic!("Invalid state!")
+        }
+    }
+}
+
+impl<S, L, R, C> ExprTerm<S, C> for ExprEither<S, L, R>
+where
+    S: Opset,
+    L: ExprTerm<S, C>,
+    R: ExprTerm<S, C>,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        match self {
+            ExprEither::Left(e) => e.to_term(),
+            ExprEither::Right(e) => e.to_term(),
             ExprEither::Never(..) => panic!("Invalid state!")
         }
     }
@@ -92,6 +149,40 @@ where
     }
 }
 
+impl<S, L, R, C> ExprWith<S, C> for ExprEither<S, L, R>
+where
+    S: Opset,
+    L: ExprWith<S, C>,
+    R: ExprWith<S, C>,
+    C: ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        match self {
+            ExprEither::Left(e) => e.eval_with(ctx),
+            ExprEither::Right(e) => e.eval_with(ctx),
+            ExprEither::Never(..) => panic!("Invalid state!")
+        }
+    }
+}
+
+impl<S, L, R, B> ExprBatch<S, B> for ExprEither<S, L, R>
+where
+    S: Opset,
+    L: ExprBatch<S, B>,
+    R: ExprBatch<S, B>,
+    B: ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        match self {
+            ExprEither::Left(e) => e.eval_batch(cols, out),
+            ExprEither::Right(e) => e.eval_batch(cols, out),
+            ExprEither::Never(..) => panic!("Invalid state!")
+        }
+    }
+}
+
 impl_fuzzy_expr_ops! {
     ExprEither<S, L, R>
 }
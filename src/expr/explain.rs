@@ -0,0 +1,203 @@
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Node of an evaluation trace.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Step {
+    /// Value leaf.
+    Value,
+    /// Tagged value leaf, with its tag.
+    Tagged(String),
+    /// Variable leaf, with its index.
+    Var(String),
+    /// Negation.
+    Not,
+    /// Conjunction.
+    And,
+    /// Alternative.
+    Or,
+    /// Modifier, with its name and parameters.
+    Apply(String)
+}
+
+
+/// Evaluation trace of an expression.
+///
+/// Mirrors the expression's tree, with the membership of every node
+/// recorded. Displays as indented text, one node per line, with values
+/// rounded to the formatter's precision if given:
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use fuzzy_systems::{Expr, Explanation, Hamacher1, Membership};
+/// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+/// let b = Expr::new(0.6);
+/// let c = Expr::var("c");
+/// let d = (a | b) & !c;
+///
+/// let mut input = HashMap::new();
+/// input.insert("c", Membership::new(0.8));
+/// let trace = Explanation::eval_with(&d, &input);
+/// assert_eq!(format!("{:.3}", trace), "\
+/// and = 0.128
+///   or = 0.640
+///     a = 0.100
+///     0.600
+///   not = 0.200
+///     c = 0.800
+/// ");
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct Explanation<S: Opset> {
+    step: Step,
+    value: Membership<S>,
+    children: Vec<Explanation<S>>
+}
+
+impl<S: Opset> Explanation<S> {
+    /// Traces evaluation of expression without variables.
+    ///
+    /// Expressions with variables need `eval_with`:
+    ///
+    /// ```compile_fail
+    /// # use fuzzy_systems::{Expr, Explanation, Hamacher1};
+    /// let d = Expr::<Hamacher1>::new(0.1) & Expr::var("c");
+    /// let trace = Explanation::eval(&d);
+    /// ```
+    pub fn eval<E>(expr: &E) -> Self
+    where
+        E: Expr<S> + ExprVisit<S, !> + ?Sized
+    {
+        Explainer.fold(expr)
+    }
+
+    /// Traces evaluation of expression with the given input.
+    pub fn eval_with<C, E>(expr: &E, ctx: &C) -> Self
+    where
+        C: ?Sized,
+        E: ExprVisit<S, C> + ?Sized
+    {
+        Explainer.fold_with(expr, ctx)
+    }
+
+    /// Kind of the node.
+    pub fn step(&self) -> &Step {
+        &self.step
+    }
+
+    /// Membership of the node.
+    pub fn value(&self) -> Membership<S> {
+        self.value
+    }
+
+    /// Traces of the operands.
+    pub fn children(&self) -> &[Explanation<S>] {
+        &self.children
+    }
+
+    fn fmt_indented(&self, f: &mut Formatter, depth: usize) -> FmtResult {
+        write!(f, "{:1$}", "", 2 * depth)?;
+        match &self.step {
+            Step::Value => {},
+            Step::Tagged(name) | Step::Var(name) | Step::Apply(name) => {
+                write!(f, "{} = ", name)?
+            },
+            Step::Not => f.write_str("not = ")?,
+            Step::And => f.write_str("and = ")?,
+            Step::Or => f.write_str("or = ")?
+        }
+        match f.precision() {
+            Some(prec) => writeln!(f, "{:.*}", prec, self.value)?,
+            None => writeln!(f, "{}", self.value)?
+        }
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Opset> Debug for Explanation<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Explanation")
+            .field("step", &self.step)
+            .field("value", &self.value)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+impl<S: Opset> Display for Explanation<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.fmt_indented(f, 0)
+    }
+}
+
+
+/// Fold recording evaluation trace.
+struct Explainer;
+
+impl Explainer {
+    fn node<S: Opset>(
+        step: Step,
+        value: Membership<S>,
+        children: Vec<Explanation<S>>
+    ) -> Explanation<S> {
+        Explanation {
+            step,
+            value,
+            children
+        }
+    }
+}
+
+impl<S: Opset> Fold<S> for Explainer {
+    type Output = Explanation<S>;
+
+    fn value(&mut self, val: Membership<S>) -> Explanation<S> {
+        Self::node(Step::Value, val, Vec::new())
+    }
+
+    fn tagged(&mut self, tag: &dyn Display, val: Membership<S>)
+        -> Explanation<S>
+    {
+        Self::node(Step::Tagged(tag.to_string()), val, Vec::new())
+    }
+
+    fn var(&mut self, idx: &dyn Display, val: Option<Membership<S>>)
+        -> Explanation<S>
+    {
+        let val = val.expect("Variable without context!");
+        Self::node(Step::Var(idx.to_string()), val, Vec::new())
+    }
+
+    fn not(&mut self, val: Explanation<S>) -> Explanation<S> {
+        Self::node(Step::Not, S::not(val.value), vec![val])
+    }
+
+    fn and(&mut self, lhs: Explanation<S>, rhs: Explanation<S>)
+        -> Explanation<S>
+    {
+        let value = S::and(lhs.value, rhs.value);
+        Self::node(Step::And, value, vec![lhs, rhs])
+    }
+
+    fn or(&mut self, lhs: Explanation<S>, rhs: Explanation<S>)
+        -> Explanation<S>
+    {
+        let value = S::or(lhs.value, rhs.value);
+        Self::node(Step::Or, value, vec![lhs, rhs])
+    }
+
+    fn apply(&mut self, op: &dyn Modifier<S>, val: Explanation<S>)
+        -> Explanation<S>
+    {
+        let value = op.apply(val.value);
+        Self::node(Step::Apply(op.to_string()), value, vec![val])
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt::{Display, Write};
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Graphviz DOT exporter of expression trees.
+///
+/// Each node is labelled by its operator (or tag, value or variable
+/// for leaves) and optionally by its evaluated membership:
+///
+/// ```
+/// # use fuzzy_systems::{DotExporter, Expr, Hamacher1};
+/// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+/// let b = Expr::new(0.6).with_tag("b");
+/// let d = !a & b;
+///
+/// let dot = DotExporter::new().with_values(true).export(&d);
+/// assert_eq!(dot, "\
+/// digraph expr {
+///     n0 [label=\"a\\n0.1\"];
+///     n1 [label=\"¬\\n0.9\"];
+///     n1 -> n0;
+///     n2 [label=\"b\\n0.6\"];
+///     n3 [label=\"∧\\n0.54\"];
+///     n3 -> n1;
+///     n3 -> n2;
+/// }
+/// ");
+/// ```
+///
+/// LaTeX math is exported via `ExprFormatter` with `Notation::Latex`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DotExporter {
+    notation: Notation,
+    values: bool
+}
+
+impl Default for DotExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DotExporter {
+    /// Creates exporter with logic symbols and no values.
+    pub fn new() -> Self {
+        Self {
+            notation: Notation::Logic,
+            values: false
+        }
+    }
+
+    /// Sets operator symbols.
+    pub fn notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Sets whether nodes are labelled with their values.
+    pub fn with_values(mut self, values: bool) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Exports expression, without values of its variables.
+    pub fn export<S, E>(&self, expr: &E) -> String
+    where
+        S: Opset,
+        E: ExprVisit<S, !> + ?Sized
+    {
+        self.graph().fold(expr)
+    }
+
+    /// Exports expression, evaluated with the given input.
+    pub fn export_with<S, C, E>(&self, expr: &E, ctx: &C) -> String
+    where
+        S: Opset,
+        C: ?Sized,
+        E: ExprVisit<S, C> + ?Sized
+    {
+        self.graph().fold_with(expr, ctx)
+    }
+
+    fn graph(&self) -> DotGraph<'_> {
+        DotGraph {
+            exporter: self,
+            out: String::from("digraph expr {\n"),
+            next: 0
+        }
+    }
+}
+
+
+/// DOT graph being exported, folded from its leaves.
+struct DotGraph<'a> {
+    exporter: &'a DotExporter,
+    out: String,
+    next: usize
+}
+
+/// Folded node: its id and value, if known.
+type DotNode<S> = (usize, Option<Membership<S>>);
+
+impl<'a> DotGraph<'a> {
+    fn fold<S, E>(mut self, expr: &E) -> String
+    where
+        S: Opset,
+        E: ExprVisit<S, !> + ?Sized
+    {
+        Fold::<S>::fold(&mut self, expr);
+        self.out.push_str("}\n");
+        self.out
+    }
+
+    fn fold_with<S, C, E>(mut self, expr: &E, ctx: &C) -> String
+    where
+        S: Opset,
+        C: ?Sized,
+        E: ExprVisit<S, C> + ?Sized
+    {
+        Fold::<S>::fold_with(&mut self, expr, ctx);
+        self.out.push_str("}\n");
+        self.out
+    }
+
+    fn node<S, L>(
+        &mut self,
+        label: &L,
+        val: Option<Membership<S>>,
+        children: &[usize]
+    ) -> DotNode<S>
+    where
+        S: Opset,
+        L: Display + ?Sized
+    {
+        let id = self.next;
+        self.next += 1;
+
+        let mut text = label.to_string();
+        if let (true, Some(val)) = (self.exporter.values, val) {
+            write!(text, "\n{}", val).unwrap();
+        }
+        let text = text
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+
+        writeln!(self.out, "    n{} [label=\"{}\"];", id, text).unwrap();
+        for child in children {
+            writeln!(self.out, "    n{} -> n{};", id, child).unwrap();
+        }
+        (id, val)
+    }
+}
+
+impl<'a, S: Opset> Fold<S> for DotGraph<'a> {
+    type Output = DotNode<S>;
+
+    fn value(&mut self, val: Membership<S>) -> DotNode<S> {
+        self.node(&val, Some(val), &[])
+    }
+
+    fn tagged(&mut self, tag: &dyn Display, val: Membership<S>)
+        -> DotNode<S>
+    {
+        self.node(tag, Some(val), &[])
+    }
+
+    fn var(&mut self, idx: &dyn Display, val: Option<Membership<S>>)
+        -> DotNode<S>
+    {
+        self.node(idx, val, &[])
+    }
+
+    fn not(&mut self, val: DotNode<S>) -> DotNode<S> {
+        let label = self.exporter.notation.not().trim();
+        self.node(label, val.1.map(S::not), &[val.0])
+    }
+
+    fn and(&mut self, lhs: DotNode<S>, rhs: DotNode<S>) -> DotNode<S> {
+        let label = self.exporter.notation.and().trim();
+        let val = lhs.1.zip(rhs.1).map(|(l, r)| S::and(l, r));
+        self.node(label, val, &[lhs.0, rhs.0])
+    }
+
+    fn or(&mut self, lhs: DotNode<S>, rhs: DotNode<S>) -> DotNode<S> {
+        let label = self.exporter.notation.or().trim();
+        let val = lhs.1.zip(rhs.1).map(|(l, r)| S::or(l, r));
+        self.node(label, val, &[lhs.0, rhs.0])
+    }
+
+    fn apply(&mut self, op: &dyn Modifier<S>, val: DotNode<S>) -> DotNode<S> {
+        self.node(op, val.1.map(|val| op.apply(val)), &[val.0])
+    }
+}
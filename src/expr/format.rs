@@ -0,0 +1,388 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use super::OperandsFormat;
+
+
+/// Binding strength of an expression, from the weakest.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Precedence {
+    Or,
+    And,
+    Not,
+    Atom
+}
+
+/// Operator symbols used by `ExprFormatter`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Notation {
+    /// `!a`, `a & b`, `a | b`
+    Ascii,
+    /// `not a`, `a and b`, `a or b`
+    Words,
+    /// `¬a`, `a ∧ b`, `a ∨ b`
+    Logic,
+    /// `\lnot a`, `a \land b`, `a \lor b`, for LaTeX math mode
+    Latex
+}
+
+impl Notation {
+    pub(crate) fn not(self) -> &'static str {
+        match self {
+            Notation::Ascii => "!",
+            Notation::Words => "not ",
+            Notation::Logic => "¬",
+            Notation::Latex => "\\lnot "
+        }
+    }
+
+    pub(crate) fn and(self) -> &'static str {
+        match self {
+            Notation::Ascii => " & ",
+            Notation::Words => " and ",
+            Notation::Logic => " ∧ ",
+            Notation::Latex => " \\land "
+        }
+    }
+
+    pub(crate) fn or(self) -> &'static str {
+        match self {
+            Notation::Ascii => " | ",
+            Notation::Words => " or ",
+            Notation::Logic => " ∨ ",
+            Notation::Latex => " \\lor "
+        }
+    }
+}
+
+
+impl Precedence {
+    /// Precedence of n-ary operator `op` applied to `ops`.
+    pub(crate) fn nary<L>(ops: &L, op: Precedence) -> Precedence
+    where
+        L: OperandsFormat + ?Sized
+    {
+        match ops.len() {
+            0 => Precedence::Atom,
+            1 => {
+                let mut prec = op;
+                let _ = ops.each_format(&mut |expr| {
+                    prec = expr.precedence();
+                    Ok(())
+                });
+                prec
+            },
+            _ => op
+        }
+    }
+}
+
+
+/// Expression printable by `ExprFormatter`.
+///
+/// Implemented by all standard expressions.
+pub trait ExprFormat {
+    /// Binding strength of the expression's outermost operator.
+    fn precedence(&self) -> Precedence;
+
+    /// Writes the expression in the formatter's style.
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult;
+}
+
+
+/// Configurable expression printer.
+///
+/// The default style matches `Display`. Parentheses can be reduced
+/// to those required by precedence and (left) associativity, operators
+/// printed as words or logic symbols and tags printed with their values:
+///
+/// ```
+/// # use fuzzy_systems::{Expr, ExprFormatter, Hamacher1, Notation};
+/// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+/// let b = Expr::new(0.6).with_tag("b");
+/// let c = Expr::new(0.4).with_tag("c");
+/// let d = (a | b) | !(b & c) & a;
+/// assert_eq!(d.to_string(), "((a | b) | (!(b & c) & a))");
+///
+/// let fmt = ExprFormatter::new().minimal_parens(true);
+/// assert_eq!(fmt.display(&d).to_string(), "a | b | !(b & c) & a");
+///
+/// let fmt = fmt.notation(Notation::Logic);
+/// assert_eq!(fmt.display(&d).to_string(), "a ∨ b ∨ ¬(b ∧ c) ∧ a");
+///
+/// let fmt = fmt.notation(Notation::Words).with_values(true);
+/// assert_eq!(
+///     fmt.display(&d).to_string(),
+///     "a=0.1 or b=0.6 or not (b=0.6 and c=0.4) and a=0.1"
+/// );
+///
+/// let fmt = fmt.notation(Notation::Latex).with_values(false);
+/// assert_eq!(
+///     fmt.display(&d).to_string(),
+///     r"\mathrm{a} \lor \mathrm{b} \lor \lnot (\mathrm{b} \land \mathrm{c}) \land \mathrm{a}"
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ExprFormatter {
+    notation: Notation,
+    minimal_parens: bool,
+    values: bool
+}
+
+impl Default for ExprFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExprFormatter {
+    /// Creates formatter in the `Display` style.
+    pub fn new() -> Self {
+        Self {
+            notation: Notation::Ascii,
+            minimal_parens: false,
+            values: false
+        }
+    }
+
+    /// Sets operator symbols.
+    pub fn notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Sets whether only required parentheses are printed.
+    pub fn minimal_parens(mut self, minimal: bool) -> Self {
+        self.minimal_parens = minimal;
+        self
+    }
+
+    /// Sets whether tags are printed along with their values.
+    pub fn with_values(mut self, values: bool) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Whether tags are printed along with their values.
+    pub fn values(&self) -> bool {
+        self.values
+    }
+
+    /// Displayable expression in the formatter's style.
+    pub fn display<'a, E>(&'a self, expr: &'a E) -> Formatted<'a, E>
+    where
+        E: ExprFormat + ?Sized
+    {
+        Formatted {
+            fmt: self,
+            expr
+        }
+    }
+
+    /// Writes negation of `val`.
+    pub fn fmt_not<V>(&self, f: &mut Formatter, val: &V) -> FmtResult
+    where
+        V: ExprFormat + ?Sized
+    {
+        f.write_str(self.notation.not())?;
+        self.fmt_operand(f, val, Precedence::Not)
+    }
+
+    /// Writes conjunction of `lhs` and `rhs`.
+    pub fn fmt_and<L, R>(&self, f: &mut Formatter, lhs: &L, rhs: &R)
+        -> FmtResult
+    where
+        L: ExprFormat + ?Sized,
+        R: ExprFormat + ?Sized
+    {
+        self.fmt_binary(f, lhs, rhs, Precedence::And, self.notation.and())
+    }
+
+    /// Writes alternative of `lhs` and `rhs`.
+    pub fn fmt_or<L, R>(&self, f: &mut Formatter, lhs: &L, rhs: &R)
+        -> FmtResult
+    where
+        L: ExprFormat + ?Sized,
+        R: ExprFormat + ?Sized
+    {
+        self.fmt_binary(f, lhs, rhs, Precedence::Or, self.notation.or())
+    }
+
+    /// Writes conjunction of all `ops`, `1` if there are none.
+    pub fn fmt_all<L>(&self, f: &mut Formatter, ops: &L) -> FmtResult
+    where
+        L: OperandsFormat + ?Sized
+    {
+        self.fmt_nary(f, ops, Precedence::And, self.notation.and(), "1")
+    }
+
+    /// Writes alternative of all `ops`, `0` if there are none.
+    pub fn fmt_any<L>(&self, f: &mut Formatter, ops: &L) -> FmtResult
+    where
+        L: OperandsFormat + ?Sized
+    {
+        self.fmt_nary(f, ops, Precedence::Or, self.notation.or(), "0")
+    }
+
+    /// Writes function `name` applied to `val` and further `args`.
+    pub fn fmt_call<V>(
+        &self,
+        f: &mut Formatter,
+        name: &str,
+        val: &V,
+        args: &[&dyn Display]
+    ) -> FmtResult
+    where
+        V: ExprFormat + ?Sized
+    {
+        self.fmt_ident(f, name)?;
+        f.write_str("(")?;
+        val.fmt_with(self, f)?;
+        for arg in args {
+            write!(f, ", {}", arg)?;
+        }
+        f.write_str(")")
+    }
+
+    /// Writes tag, along with the value if enabled.
+    pub fn fmt_tag<T, V>(&self, f: &mut Formatter, tag: &T, val: &V)
+        -> FmtResult
+    where
+        T: Display + ?Sized,
+        V: Display + ?Sized
+    {
+        self.fmt_ident(f, tag)?;
+        if self.values {
+            write!(f, "={}", val)?;
+        }
+        Ok(())
+    }
+
+    /// Writes variable.
+    pub fn fmt_var<I>(&self, f: &mut Formatter, idx: &I) -> FmtResult
+    where
+        I: Display + ?Sized
+    {
+        self.fmt_ident(f, idx)
+    }
+
+    fn fmt_ident<I>(&self, f: &mut Formatter, ident: &I) -> FmtResult
+    where
+        I: Display + ?Sized
+    {
+        if self.notation != Notation::Latex {
+            return write!(f, "{}", ident);
+        }
+
+        f.write_str("\\mathrm{")?;
+        for c in ident.to_string().chars() {
+            match c {
+                '_' | '&' | '%' | '$' | '#' | '{' | '}' => write!(f, "\\{}", c)?,
+                '\\' => f.write_str("\\textbackslash{}")?,
+                '^' | '~' => write!(f, "\\{}{{}}", c)?,
+                _ => write!(f, "{}", c)?
+            }
+        }
+        f.write_str("}")
+    }
+
+    fn fmt_binary<L, R>(
+        &self,
+        f: &mut Formatter,
+        lhs: &L,
+        rhs: &R,
+        prec: Precedence,
+        op: &str
+    ) -> FmtResult
+    where
+        L: ExprFormat + ?Sized,
+        R: ExprFormat + ?Sized
+    {
+        // left-associative: right operand has to bind stronger
+        let rhs_prec = match prec {
+            Precedence::Or => Precedence::And,
+            _ => Precedence::Not
+        };
+
+        if !self.minimal_parens {
+            f.write_str("(")?;
+        }
+        self.fmt_operand(f, lhs, prec)?;
+        f.write_str(op)?;
+        self.fmt_operand(f, rhs, rhs_prec)?;
+        if !self.minimal_parens {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+
+    fn fmt_nary<L>(
+        &self,
+        f: &mut Formatter,
+        ops: &L,
+        prec: Precedence,
+        op: &str,
+        empty: &str
+    ) -> FmtResult
+    where
+        L: OperandsFormat + ?Sized
+    {
+        match ops.len() {
+            0 => return f.write_str(empty),
+            1 => return ops.each_format(&mut |expr| expr.fmt_with(self, f)),
+            _ => {}
+        }
+
+        // same as left-associative binary operators
+        let rhs_prec = match prec {
+            Precedence::Or => Precedence::And,
+            _ => Precedence::Not
+        };
+
+        if !self.minimal_parens {
+            f.write_str("(")?;
+        }
+        let mut first = true;
+        ops.each_format(&mut |expr| {
+            if first {
+                first = false;
+                self.fmt_operand(f, expr, prec)
+            }
+            else {
+                f.write_str(op)?;
+                self.fmt_operand(f, expr, rhs_prec)
+            }
+        })?;
+        if !self.minimal_parens {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+
+    fn fmt_operand<E>(&self, f: &mut Formatter, expr: &E, min: Precedence)
+        -> FmtResult
+    where
+        E: ExprFormat + ?Sized
+    {
+        if self.minimal_parens && expr.precedence() < min {
+            f.write_str("(")?;
+            expr.fmt_with(self, f)?;
+            f.write_str(")")
+        }
+        else {
+            expr.fmt_with(self, f)
+        }
+    }
+}
+
+
+/// Expression displayed by `ExprFormatter`.
+pub struct Formatted<'a, E: ?Sized> {
+    fmt: &'a ExprFormatter,
+    expr: &'a E
+}
+
+impl<'a, E: ExprFormat + ?Sized> Display for Formatted<'a, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.expr.fmt_with(self.fmt, f)
+    }
+}
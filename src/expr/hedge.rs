@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::{Membership, Raw};
+use crate::impl_fuzzy_expr_ops;
+
+
+/// Linguistic hedge, see `ExprHedge`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Hedge {
+    /// Concentration, `a^2`.
+    Very,
+    /// `a^3`.
+    Extremely,
+    /// Dilation, `a^0.5`.
+    Somewhat,
+    /// `a^1.7`.
+    Slightly,
+    /// Contrast intensification.
+    Indeed,
+    /// Any power, `a^exp`.
+    Pow(Raw)
+}
+
+impl Hedge {
+    fn name(self) -> &'static str {
+        match self {
+            Hedge::Very => "very",
+            Hedge::Extremely => "extremely",
+            Hedge::Somewhat => "somewhat",
+            Hedge::Slightly => "slightly",
+            Hedge::Indeed => "indeed",
+            Hedge::Pow(_) => "pow"
+        }
+    }
+}
+
+impl Display for Hedge {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Hedge::Pow(exp) => write!(f, "pow({})", exp),
+            _ => f.write_str(self.name())
+        }
+    }
+}
+
+impl<S: Opset> Modifier<S> for Hedge {
+    #[inline]
+    fn apply(&self, val: Membership<S>) -> Membership<S> {
+        match *self {
+            Hedge::Very => val.concentrate(),
+            Hedge::Extremely => val.pow(3.0),
+            Hedge::Somewhat => val.dilate(),
+            Hedge::Slightly => val.pow(1.7),
+            Hedge::Indeed => val.intensify(),
+            Hedge::Pow(exp) => val.pow(exp)
+        }
+    }
+}
+
+
+/// Fuzzy expression modified by a linguistic hedge.
+///
+/// ```
+/// # use fuzzy_systems::{Expr, Hamacher1, Hedge};
+/// let hot = Expr::<Hamacher1>::new(0.8).with_tag("hot");
+/// let cold = Expr::new(0.3).with_tag("cold");
+///
+/// let d = hot.hedge(Hedge::Very) & !cold.hedge(Hedge::Pow(1.5));
+/// assert_eq!(d.to_string(), "(very(hot) & !pow(cold, 1.5))");
+///
+/// let very_hot = hot.hedge(Hedge::Very);
+/// assert!((very_hot.to_value().as_raw() - 0.64).abs() < 1e-6);
+/// ```
+pub struct ExprHedge<S: Opset, E> {
+    expr: E,
+    hedge: Hedge,
+    phantom: PhantomData<S>
+}
+
+impl<S: Opset, E> ExprHedge<S, E> {
+    /// Creates expression.
+    /// Panics if the hedge is a negative or NaN power.
+    pub fn new(expr: E, hedge: Hedge) -> Self {
+        if let Hedge::Pow(exp) = hedge {
+            assert!(exp >= 0.0);
+        }
+        Self {
+            expr,
+            hedge,
+            phantom: PhantomData
+        }
+    }
+
+    /// Modified expression.
+    pub fn expr(&self) -> &E {
+        &self.expr
+    }
+
+    /// Applied hedge.
+    pub fn hedge(&self) -> Hedge {
+        self.hedge
+    }
+}
+
+impl<S: Opset, E: Clone> Clone for ExprHedge<S, E> {
+    fn clone(&self) -> Self {
+        Self::new(self.expr.clone(), self.hedge)
+    }
+}
+
+impl<S: Opset, E: Copy> Copy for ExprHedge<S, E> {}
+
+impl<S: Opset, E: Debug> Debug for ExprHedge<S, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "ExprHedge({:?}, {:?})", self.expr, self.hedge)
+    }
+}
+
+impl<S: Opset, E: ExprFormat> Display for ExprHedge<S, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.fmt_with(&ExprFormatter::new(), f)
+    }
+}
+
+impl<S: Opset, E: ExprFormat> ExprFormat for ExprHedge<S, E> {
+    fn precedence(&self) -> Precedence {
+        Precedence::Atom
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        match &self.hedge {
+            Hedge::Pow(exp) => fmt.fmt_call(f, "pow", &self.expr, &[exp]),
+            hedge => fmt.fmt_call(f, hedge.name(), &self.expr, &[])
+        }
+    }
+}
+
+impl<S, E, C> ExprVisit<S, C> for ExprHedge<S, E>
+where
+    S: Opset,
+    E: ExprVisit<S, C>,
+    C: ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        self.expr.visit(ctx, visitor);
+        visitor.apply(&self.hedge);
+    }
+}
+
+impl<S, E, C> ExprTerm<S, C> for ExprHedge<S, E>
+where
+    S: Opset + 'static,
+    E: DynExpr<S, C> + Clone + 'static,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::leaf(self.clone())
+    }
+
+    fn same_leaf(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.hedge == other.hedge
+                && self.expr.to_term().same(&other.expr.to_term())
+        })
+    }
+}
+
+impl<S, E> Expr<S> for ExprHedge<S, E>
+where
+    S: Opset,
+    E: Expr<S>
+{
+    #[inline]
+    fn to_value(&self) -> Membership<S> {
+        self.hedge.apply(self.expr.to_value())
+    }
+}
+
+impl<S, E, C> ExprWith<S, C> for ExprHedge<S, E>
+where
+    S: Opset,
+    E: ExprWith<S, C>,
+    C: ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        self.hedge.apply(self.expr.eval_with(ctx))
+    }
+}
+
+impl<S, E, B> ExprBatch<S, B> for ExprHedge<S, E>
+where
+    S: Opset,
+    E: ExprBatch<S, B>,
+    B: ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        self.expr.eval_batch(cols, out);
+        for val in out {
+            *val = self.hedge.apply(*val);
+        }
+    }
+}
+
+impl_fuzzy_expr_ops! {
+    ExprHedge<S, E>
+}
@@ -11,91 +11,262 @@
 ///     R: Expr<S>
 /// {
 ///     fuzzy_math!(lhs | rhs)
-///     // expands to: lhs.or(rhs)
+///     // builds: ExprOr::new(lhs, rhs)
 ///     // without macro, the following fails: lhs | rhs
 /// }
 /// ```
 ///
 /// Enables operator syntax in generic scenarios.
+///
+/// Supports long expressions (the recursion depth grows by about one
+/// level per eight tokens), with `!` binding stronger than `&` and `&`
+/// binding stronger than `|` (both left-associative). Operands are
+/// arbitrary Rust expressions (method calls, field accesses etc),
+/// parenthesized fuzzy expressions or literals (converted via
+/// `Expr::new`, integers included):
+///
+/// ```
+/// # use fuzzy_systems::{Expr, ExprValue, Hamacher1, fuzzy_math};
+/// struct Rule {
+///     a: ExprValue<Hamacher1>,
+///     b: ExprValue<Hamacher1>
+/// }
+///
+/// let rule = Rule { a: Expr::new(0.1), b: Expr::new(0.6) };
+/// let c = Expr::new(0.4);
+/// let d = fuzzy_math!(rule.a & rule.b | !c.with_tag("c") & 0.5 | 1.0);
+/// assert_eq!(d.to_string(), "(((0.1 & 0.6) | (!c & 0.5)) | 1)");
+/// ```
 #[macro_export]
 macro_rules! fuzzy_math {
-    // ident
-    ($a:ident) => {
-        $a
+    // `or`: split on top-level `|`, up to 8 tokens per step to keep
+    // the recursion shallow, then chain the groups left to right.
+    (@or [$($g:tt)*] [$($c:tt)*] | $($r:tt)*) => {
+        $crate::fuzzy_math!(@or [$($g)* [$($c)*]] [] $($r)*)
+    };
+    (@or [$($g:tt)*] [$($c:tt)*] $t1:tt | $($r:tt)*) => {
+        $crate::fuzzy_math!(@or [$($g)* [$($c)* $t1]] [] $($r)*)
+    };
+    (@or [$($g:tt)*] [$($c:tt)*] $t1:tt $t2:tt | $($r:tt)*) => {
+        $crate::fuzzy_math!(@or [$($g)* [$($c)* $t1 $t2]] [] $($r)*)
+    };
+    (@or [$($g:tt)*] [$($c:tt)*] $t1:tt $t2:tt $t3:tt | $($r:tt)*) => {
+        $crate::fuzzy_math!(@or [$($g)* [$($c)* $t1 $t2 $t3]] [] $($r)*)
+    };
+    (@or [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt | $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @or [$($g)* [$($c)* $t1 $t2 $t3 $t4]] [] $($r)*
+        )
+    };
+    (@or [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt | $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @or [$($g)* [$($c)* $t1 $t2 $t3 $t4 $t5]] [] $($r)*
+        )
+    };
+    (@or [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt | $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @or [$($g)* [$($c)* $t1 $t2 $t3 $t4 $t5 $t6]] [] $($r)*
+        )
+    };
+    (@or [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt | $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @or [$($g)* [$($c)* $t1 $t2 $t3 $t4 $t5 $t6 $t7]] [] $($r)*
+        )
+    };
+    (@or [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt
+        $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @or [$($g)*] [$($c)* $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $($r)*
+        )
+    };
+    (@or [$($g:tt)*] [$($c:tt)*] $t:tt $($r:tt)*) => {
+        $crate::fuzzy_math!(@or [$($g)*] [$($c)* $t] $($r)*)
+    };
+    (@or [[$($first:tt)*] $([$($g:tt)*])*] [$($c:tt)*]) => {
+        $crate::fuzzy_math!(@and [] [] $($first)*)
+            $(.or($crate::fuzzy_math!(@and [] [] $($g)*)))*
+            .or($crate::fuzzy_math!(@and [] [] $($c)*))
+    };
+    (@or [] [$($c:tt)*]) => {
+        $crate::fuzzy_math!(@and [] [] $($c)*)
     };
 
 
-    // `not`
-    (!$a:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            (fuzzy_math!($a.not()))
-            $($rest)*
-        }
+    // `and`: the same, splitting on top-level `&`.
+    (@and [$($g:tt)*] [$($c:tt)*] & $($r:tt)*) => {
+        $crate::fuzzy_math!(@and [$($g)* [$($c)*]] [] $($r)*)
+    };
+    (@and [$($g:tt)*] [$($c:tt)*] $t1:tt & $($r:tt)*) => {
+        $crate::fuzzy_math!(@and [$($g)* [$($c)* $t1]] [] $($r)*)
+    };
+    (@and [$($g:tt)*] [$($c:tt)*] $t1:tt $t2:tt & $($r:tt)*) => {
+        $crate::fuzzy_math!(@and [$($g)* [$($c)* $t1 $t2]] [] $($r)*)
+    };
+    (@and [$($g:tt)*] [$($c:tt)*] $t1:tt $t2:tt $t3:tt & $($r:tt)*) => {
+        $crate::fuzzy_math!(@and [$($g)* [$($c)* $t1 $t2 $t3]] [] $($r)*)
+    };
+    (@and [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt & $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @and [$($g)* [$($c)* $t1 $t2 $t3 $t4]] [] $($r)*
+        )
+    };
+    (@and [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt & $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @and [$($g)* [$($c)* $t1 $t2 $t3 $t4 $t5]] [] $($r)*
+        )
+    };
+    (@and [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt & $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @and [$($g)* [$($c)* $t1 $t2 $t3 $t4 $t5 $t6]] [] $($r)*
+        )
+    };
+    (@and [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt & $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @and [$($g)* [$($c)* $t1 $t2 $t3 $t4 $t5 $t6 $t7]] [] $($r)*
+        )
+    };
+    (@and [$($g:tt)*] [$($c:tt)*]
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt
+        $($r:tt)*) => {
+        $crate::fuzzy_math!(
+            @and [$($g)*] [$($c)* $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $($r)*
+        )
+    };
+    (@and [$($g:tt)*] [$($c:tt)*] $t:tt $($r:tt)*) => {
+        $crate::fuzzy_math!(@and [$($g)*] [$($c)* $t] $($r)*)
+    };
+    (@and [[$($first:tt)*] $([$($g:tt)*])*] [$($c:tt)*]) => {
+        $crate::fuzzy_math!(@not $($first)*)
+            $(.and($crate::fuzzy_math!(@not $($g)*)))*
+            .and($crate::fuzzy_math!(@not $($c)*))
+    };
+    (@and [] [$($c:tt)*]) => {
+        $crate::fuzzy_math!(@not $($c)*)
     };
-    
 
-    // `or`
-    ($a:tt | !$b:tt & $c:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            $a | (fuzzy_math!($b).not().and(fuzzy_math!($c)))
-            $($rest)*
-        }
+
+    // `not` and operands.
+    (@not ! $($rest:tt)+) => {
+        $crate::fuzzy_math!(@not $($rest)+).not()
     };
 
-    ($a:tt | $b:tt & !$c:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            $a | (fuzzy_math!($b).and(fuzzy_math!($c).not()))
-            $($rest)*
-        }
+    (@not $lit:literal) => {
+        $crate::fuzzy_math!(
+            @operand $crate::expr::ExprValue::new($lit as $crate::Raw)
+        )
     };
 
-    ($a:tt | $b:tt & $c:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            $a | (fuzzy_math!($b).and(fuzzy_math!($c)))
-            $($rest)*
-        }
+    (@not ($($inner:tt)+)) => {
+        $crate::fuzzy_math!(@or [] [] $($inner)+)
     };
 
+    (@not $($operand:tt)+) => {
+        $crate::fuzzy_math!(@operand $($operand)+)
+    };
 
-    ($a:tt | !$b:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            (fuzzy_math!($a).or(fuzzy_math!($b).not()))
-            $($rest)*
-        }
+    (@operand $operand:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::expr::__fuzzy_math::{ViaExpr, ViaExprWith};
+        let expr = $operand;
+        let opset = (&&$crate::expr::__fuzzy_math::Probe(&expr)).opset();
+        $crate::expr::__fuzzy_math::Operand::new(opset, expr)
+    }};
+
+
+    // entry
+    ($($tts:tt)+) => {
+        $crate::fuzzy_math!(@or [] [] $($tts)+).into_inner()
     };
+}
 
-    ($a:tt | $b:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            (fuzzy_math!($a).or(fuzzy_math!($b)))
-            $($rest)*
+/// Operands of `fuzzy_math!`, with their opset inferred from the operands.
+#[doc(hidden)]
+pub mod __fuzzy_math {
+    use std::marker::PhantomData;
+
+    use crate::expr::{Expr, ExprAnd, ExprNot, ExprOr, ExprWith};
+    use crate::opset::Opset;
+
+    /// Expression along with its opset.
+    pub struct Operand<S, E> {
+        expr: E,
+        phantom: PhantomData<S>
+    }
+
+    impl<S: Opset, E> Operand<S, E> {
+        #[inline]
+        pub fn new(_opset: PhantomData<S>, expr: E) -> Self {
+            Self {
+                expr,
+                phantom: PhantomData
+            }
+        }
+
+        #[inline]
+        pub fn into_inner(self) -> E {
+            self.expr
+        }
+
+        #[inline]
+        #[allow(clippy::should_implement_trait)]
+        pub fn not(self) -> Operand<S, ExprNot<S, E>> {
+            Operand::new(PhantomData, ExprNot::new(self.expr))
         }
-    };
 
+        #[inline]
+        pub fn and<R>(self, rhs: Operand<S, R>)
+            -> Operand<S, ExprAnd<S, E, R>> {
 
-    // `and`
-    ($a:tt & !$b:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            (fuzzy_math!($a).and(fuzzy_math!($b).not()))
-            $($rest)*
+            Operand::new(PhantomData, ExprAnd::new(self.expr, rhs.expr))
         }
-    };
 
-    ($a:tt & $b:tt $($rest:tt)*) => {
-        fuzzy_math! {
-            (fuzzy_math!($a).and(fuzzy_math!($b)))
-            $($rest)*
+        #[inline]
+        pub fn or<R>(self, rhs: Operand<S, R>)
+            -> Operand<S, ExprOr<S, E, R>> {
+
+            Operand::new(PhantomData, ExprOr::new(self.expr, rhs.expr))
         }
-    };
+    }
 
-    // parens
-    (($($a:tt)*)) => {
-        fuzzy_math!($($a)*)
-    };
+    /// Finds the opset of an operand, either from `ExprWith<S, !>`
+    /// (any standard expression, with variables or not) or, failing
+    /// that, from `Expr<S>` (e.g. a generic closed expression).
+    pub struct Probe<'a, E: ?Sized>(pub &'a E);
 
-    // recurse
-    ($($a:tt)*) => {
-        $($a)*
+    pub trait ViaExprWith<S> {
+        #[inline]
+        fn opset(&self) -> PhantomData<S> {
+            PhantomData
+        }
     }
+
+    impl<'a, S, E> ViaExprWith<S> for &Probe<'a, E>
+    where
+        S: Opset,
+        E: ExprWith<S, !> + ?Sized
+    {}
+
+    pub trait ViaExpr<S> {
+        #[inline]
+        fn opset(&self) -> PhantomData<S> {
+            PhantomData
+        }
+    }
+
+    impl<'a, S, E> ViaExpr<S> for Probe<'a, E>
+    where
+        S: Opset,
+        E: Expr<S> + ?Sized
+    {}
 }
 
 #[doc(hidden)]
@@ -104,39 +275,33 @@ macro_rules! impl_fuzzy_expr_ops {
     ($typ:ident<$S:ident $(, $params:ident)*>) => {
         impl<$S $(, $params)*> std::ops::Not for $typ<$S $(, $params)*>
         where
-            $S: Opset,
-            $($params: Expr<$S>,)*
-            Self: Sized
+            $S: Opset
         {
-            type Output = $crate::expr::ExprNot<S, Self>;
+            type Output = $crate::expr::ExprNot<$S, Self>;
             fn not(self) -> Self::Output {
-                Expr::not(self)
+                $crate::expr::ExprNot::new(self)
             }
         }
 
         impl<$S $(, $params)*, __R> std::ops::BitAnd<__R> for $typ<$S $(, $params)*>
         where
             $S: Opset,
-            $($params: Expr<$S>,)*
-            __R: Expr<S>,
-            Self: Sized
+            __R: $crate::expr::ExprWith<$S, !>
         {
-            type Output = $crate::expr::ExprAnd<S, Self, __R>;
+            type Output = $crate::expr::ExprAnd<$S, Self, __R>;
             fn bitand(self, rhs: __R) -> Self::Output {
-                Expr::and(self, rhs)
+                $crate::expr::ExprAnd::new(self, rhs)
             }
         }
 
         impl<$S $(, $params)*, __R> std::ops::BitOr<__R> for $typ<$S $(, $params)*>
         where
             $S: Opset,
-            $($params: Expr<$S>,)*
-            __R: Expr<S>,
-            Self: Sized
+            __R: $crate::expr::ExprWith<$S, !>
         {
-            type Output = $crate::expr::ExprOr<S, Self, __R>;
+            type Output = $crate::expr::ExprOr<$S, Self, __R>;
             fn bitor(self, rhs: __R) -> Self::Output {
-                Expr::or(self, rhs)
+                $crate::expr::ExprOr::new(self, rhs)
             }
         }
     };
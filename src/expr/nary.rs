@@ -0,0 +1,245 @@
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+
+use super::{
+    Expr, ExprBatch, ExprFormat, ExprFormatter, ExprTerm, ExprVisit, ExprWith,
+    OperandsBatch, OperandsFormat, OperandsTerm, OperandsValue, OperandsVisit,
+    OperandsWith, Precedence, Term, Visitor
+};
+use super::batch::scratch;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+use crate::impl_fuzzy_expr_ops;
+
+
+macro_rules! impl_nary {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident {
+            op: $op:ident,
+            slice: $slice:ident,
+            term: $term:ident,
+            prec: $prec:ident,
+            fmt: $fmt:ident,
+            empty: $empty:literal
+        }
+    )*) => {$(
+        $(#[$meta])*
+        pub struct $name<S, L> {
+            ops: L,
+            phantom: PhantomData<S>
+        }
+
+        impl<S, L> $name<S, L> {
+            /// Combines the operands.
+            pub fn new(ops: L) -> Self {
+                Self {
+                    ops,
+                    phantom: PhantomData
+                }
+            }
+
+            /// Operands.
+            pub fn operands(&self) -> &L {
+                &self.ops
+            }
+        }
+
+        impl<S, L: Clone> Clone for $name<S, L> {
+            fn clone(&self) -> Self {
+                Self::new(self.ops.clone())
+            }
+        }
+
+        impl<S, L: Copy> Copy for $name<S, L> {}
+
+        impl<S, L: Debug> Debug for $name<S, L> {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                write!(f, concat!(stringify!($name), "({:?})"), self.ops)
+            }
+        }
+
+        impl<S, L: OperandsFormat> Display for $name<S, L> {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                self.fmt_with(&ExprFormatter::new(), f)
+            }
+        }
+
+        impl<S, L: OperandsFormat> ExprFormat for $name<S, L> {
+            fn precedence(&self) -> Precedence {
+                Precedence::nary(&self.ops, Precedence::$prec)
+            }
+
+            fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter)
+                -> FmtResult
+            {
+                fmt.$fmt(f, &self.ops)
+            }
+        }
+
+        impl<S, L, C> ExprVisit<S, C> for $name<S, L>
+        where
+            S: Opset,
+            L: OperandsVisit<S, C>,
+            C: ?Sized
+        {
+            fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+                if self.ops.is_empty() {
+                    return visitor.value(Membership::new($empty));
+                }
+
+                let mut first = true;
+                self.ops.each_visit(&mut |expr| {
+                    expr.visit(ctx, visitor);
+                    if !first {
+                        visitor.$op();
+                    }
+                    first = false;
+                });
+            }
+        }
+
+        impl<S, L, C> ExprTerm<S, C> for $name<S, L>
+        where
+            S: Opset,
+            L: OperandsTerm<S, C>,
+            C: ?Sized
+        {
+            fn to_term(&self) -> Term<S, C> {
+                let mut acc = None;
+                self.ops.each_term(&mut |term| {
+                    acc = Some(match acc.take() {
+                        Some(lhs) => Term::$term(Box::new(lhs), Box::new(term)),
+                        None => term
+                    });
+                });
+                acc.unwrap_or_else(|| Term::Const(Membership::new($empty)))
+            }
+        }
+
+        impl<S, L> Expr<S> for $name<S, L>
+        where
+            S: Opset,
+            L: OperandsValue<S>
+        {
+            #[inline]
+            fn to_value(&self) -> Membership<S> {
+                let mut acc = None;
+                self.ops.each_value(&mut |val| {
+                    acc = Some(acc.map_or(val, |acc| S::$op(acc, val)));
+                });
+                acc.unwrap_or_else(|| Membership::new($empty))
+            }
+        }
+
+        impl<S, L, C> ExprWith<S, C> for $name<S, L>
+        where
+            S: Opset,
+            L: OperandsWith<S, C>,
+            C: ?Sized
+        {
+            #[inline]
+            fn eval_with(&self, ctx: &C) -> Membership<S> {
+                let mut acc = None;
+                self.ops.each_with(ctx, &mut |val| {
+                    acc = Some(acc.map_or(val, |acc| S::$op(acc, val)));
+                });
+                acc.unwrap_or_else(|| Membership::new($empty))
+            }
+        }
+
+        impl<S, L, B> ExprBatch<S, B> for $name<S, L>
+        where
+            S: Opset,
+            L: OperandsBatch<S, B>,
+            B: ?Sized
+        {
+            #[inline]
+            fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+                if self.ops.is_empty() {
+                    return out.fill(Membership::new($empty));
+                }
+
+                let mut first = true;
+                let mut rhs = scratch(out.len());
+                self.ops.each_batch(&mut |expr| {
+                    if first {
+                        expr.eval_batch(cols, out);
+                        first = false;
+                    }
+                    else {
+                        expr.eval_batch(cols, &mut rhs);
+                        S::$slice(out, &rhs);
+                    }
+                });
+            }
+        }
+
+        impl_fuzzy_expr_ops! {
+            $name<S, L>
+        }
+    )*};
+}
+
+impl_nary! {
+    /// Fuzzy "and" expression of any number of operands.
+    ///
+    /// Operands are held in an array, `Vec` or tuple and combined from
+    /// the left, `1` if there are none:
+    ///
+    /// ```
+    /// # use fuzzy_systems::{Expr, Hamacher1};
+    /// let a = Expr::<Hamacher1>::new(0.8).with_tag("a");
+    /// let b = Expr::new(0.5).with_tag("b");
+    /// let c = Expr::new(0.4).with_tag("c");
+    ///
+    /// let d = Expr::all((a, b, !c));
+    /// assert_eq!(d.to_string(), "(a & b & !c)");
+    /// assert_eq!(d.to_value(), (a & b & !c).to_value());
+    ///
+    /// let conditions: Vec<_> = (1..20)
+    ///     .map(|i| Expr::new(i as f64 / 20.0))
+    ///     .collect();
+    /// let d = Expr::<Hamacher1>::all(conditions);
+    /// assert!(d.to_value().as_raw() < 1e-6);
+    /// ```
+    ExprAll {
+        op: and,
+        slice: and_slice,
+        term: And,
+        prec: And,
+        fmt: fmt_all,
+        empty: 1.0
+    }
+
+    /// Fuzzy "or" expression of any number of operands.
+    ///
+    /// Operands are held in an array, `Vec` or tuple and combined from
+    /// the left, `0` if there are none:
+    ///
+    /// ```
+    /// # use fuzzy_systems::{Expr, Hamacher1};
+    /// let a = Expr::<Hamacher1>::new(0.8).with_tag("a");
+    /// let b = Expr::new(0.5).with_tag("b");
+    /// let c = Expr::new(0.4).with_tag("c");
+    ///
+    /// let d = Expr::any((a, b, !c));
+    /// assert_eq!(d.to_string(), "(a | b | !c)");
+    /// assert_eq!(d.to_value(), (a | b | !c).to_value());
+    ///
+    /// let conditions: Vec<_> = (1..20)
+    ///     .map(|i| Expr::new(i as f64 / 20.0))
+    ///     .collect();
+    /// let d = Expr::<Hamacher1>::any(conditions);
+    /// assert!(d.to_value().as_raw() > 1.0 - 1e-6);
+    /// ```
+    ExprAny {
+        op: or,
+        slice: or_slice,
+        term: Or,
+        prec: Or,
+        fmt: fmt_any,
+        empty: 0.0
+    }
+}
@@ -0,0 +1,205 @@
+use super::*;
+
+use crate::opset::OpsetLaws;
+
+
+/// Term converted into a normal form.
+///
+/// The conversion preserves the value exactly only if the opset satisfies
+/// the laws used by it (see `OpsetLaws`), otherwise the result is only
+/// structurally equivalent, which is reported by `is_exact`.
+pub struct NormalForm<S: Opset, C: ?Sized> {
+    term: Term<S, C>,
+    exact: bool
+}
+
+impl<S: Opset, C: ?Sized> NormalForm<S, C> {
+    /// Converted term.
+    pub fn term(&self) -> &Term<S, C> {
+        &self.term
+    }
+
+    /// Moves out converted term.
+    pub fn into_term(self) -> Term<S, C> {
+        self.term
+    }
+
+    /// Whether the value is preserved exactly (up to rounding).
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+impl<S: Opset + 'static, C: ?Sized + 'static> NormalForm<S, C> {
+    /// Converts back into expression.
+    pub fn to_expr(&self) -> ExprDyn<S, C> {
+        self.term.to_expr()
+    }
+}
+
+
+impl<S: OpsetLaws, C: ?Sized> Term<S, C> {
+    /// Converts into negation normal form: negations are applied
+    /// to leaves only.
+    ///
+    /// Exact for involutive opsets satisfying De Morgan's laws
+    /// (all predefined but `Hamacher2`).
+    pub fn nnf(self) -> NormalForm<S, C> {
+        let mut exact = true;
+        let term = self.push_not(false, &mut exact);
+        NormalForm { term, exact }
+    }
+
+    /// Converts into disjunctive normal form: alternative of conjunctions
+    /// of (negated) leaves.
+    ///
+    /// Distributes conjunctions over alternatives, so the term may grow
+    /// exponentially. Exact only for distributive opsets (`YagerInf`).
+    pub fn dnf(self) -> NormalForm<S, C> {
+        let mut exact = true;
+        let term = self.push_not(false, &mut exact).spread_and(&mut exact);
+        NormalForm { term, exact }
+    }
+
+    /// Converts into conjunctive normal form: conjunction of alternatives
+    /// of (negated) leaves.
+    ///
+    /// Distributes alternatives over conjunctions, so the term may grow
+    /// exponentially. Exact only for distributive opsets (`YagerInf`).
+    pub fn cnf(self) -> NormalForm<S, C> {
+        let mut exact = true;
+        let term = self.push_not(false, &mut exact).spread_or(&mut exact);
+        NormalForm { term, exact }
+    }
+
+    fn push_not(self, negate: bool, exact: &mut bool) -> Self {
+        match self {
+            Term::Const(val) if negate => Term::Const(S::not(val)),
+            Term::Not(val) => {
+                if negate && !S::INVOLUTIVE {
+                    *exact = false;
+                }
+                val.push_not(!negate, exact)
+            },
+            Term::And(lhs, rhs) if negate => {
+                *exact &= S::DE_MORGAN;
+                Term::Or(
+                    Box::new(lhs.push_not(true, exact)),
+                    Box::new(rhs.push_not(true, exact))
+                )
+            },
+            Term::Or(lhs, rhs) if negate => {
+                *exact &= S::DE_MORGAN;
+                Term::And(
+                    Box::new(lhs.push_not(true, exact)),
+                    Box::new(rhs.push_not(true, exact))
+                )
+            },
+            Term::And(lhs, rhs) => Term::And(
+                Box::new(lhs.push_not(false, exact)),
+                Box::new(rhs.push_not(false, exact))
+            ),
+            Term::Or(lhs, rhs) => Term::Or(
+                Box::new(lhs.push_not(false, exact)),
+                Box::new(rhs.push_not(false, exact))
+            ),
+            leaf if negate => Term::Not(Box::new(leaf)),
+            leaf => leaf
+        }
+    }
+
+    fn spread_and(self, exact: &mut bool) -> Self {
+        match self {
+            Term::And(lhs, rhs) => {
+                let lhs = lhs.spread_and(exact);
+                let rhs = rhs.spread_and(exact);
+                Self::distribute(lhs, rhs, exact)
+            },
+            Term::Or(lhs, rhs) => Term::Or(
+                Box::new(lhs.spread_and(exact)),
+                Box::new(rhs.spread_and(exact))
+            ),
+            literal => literal
+        }
+    }
+
+    fn spread_or(self, exact: &mut bool) -> Self {
+        // dual of DNF: swap the operators, distribute, swap back
+        self.dual().spread_and(exact).dual()
+    }
+
+    /// Distributes conjunction of DNF terms over their alternatives.
+    fn distribute(lhs: Self, rhs: Self, exact: &mut bool) -> Self {
+        match (lhs, rhs) {
+            (Term::Or(a, b), rhs) => {
+                *exact &= S::DISTRIBUTIVE;
+                Term::Or(
+                    Box::new(Self::distribute(*a, rhs.clone(), exact)),
+                    Box::new(Self::distribute(*b, rhs, exact))
+                )
+            },
+            (lhs, Term::Or(a, b)) => {
+                *exact &= S::DISTRIBUTIVE;
+                Term::Or(
+                    Box::new(Self::distribute(lhs.clone(), *a, exact)),
+                    Box::new(Self::distribute(lhs, *b, exact))
+                )
+            },
+            (lhs, rhs) => Term::And(Box::new(lhs), Box::new(rhs))
+        }
+    }
+
+    /// Swaps conjunctions and alternatives.
+    fn dual(self) -> Self {
+        match self {
+            Term::And(lhs, rhs) => {
+                Term::Or(Box::new(lhs.dual()), Box::new(rhs.dual()))
+            },
+            Term::Or(lhs, rhs) => {
+                Term::And(Box::new(lhs.dual()), Box::new(rhs.dual()))
+            },
+            literal => literal
+        }
+    }
+}
+
+impl<S: OpsetLaws, C: ?Sized + 'static> ExprDyn<S, C> {
+    /// Negation normal form (see `Term::nnf`).
+    pub fn nnf(&self) -> NormalForm<S, C> {
+        self.to_term().nnf()
+    }
+
+    /// Disjunctive normal form (see `Term::dnf`).
+    ///
+    /// ```
+    /// # use fuzzy_systems::{Expr, ExprDyn, Hamacher1, YagerInf};
+    /// let a = Expr::<YagerInf>::new(0.1).with_tag("a");
+    /// let b = Expr::new(0.6).with_tag("b");
+    /// let c = Expr::new(0.4).with_tag("c");
+    /// let d = ExprDyn::<YagerInf>::new(a & !(b & !c));
+    ///
+    /// let dnf = d.dnf();
+    /// assert_eq!(dnf.to_expr().to_string(), "((a & !b) | (a & c))");
+    /// assert!(dnf.is_exact());
+    /// assert_eq!(dnf.to_expr().to_value(), d.to_value());
+    ///
+    /// // Hamacher1 is not distributive
+    /// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+    /// let b = Expr::new(0.6).with_tag("b");
+    /// let c = Expr::new(0.4).with_tag("c");
+    /// let d = ExprDyn::<Hamacher1>::new(a & !(b & !c));
+    ///
+    /// let dnf = d.dnf();
+    /// assert_eq!(dnf.to_expr().to_string(), "((a & !b) | (a & c))");
+    /// assert!(!dnf.is_exact());
+    /// assert!(d.nnf().is_exact());
+    /// ```
+    pub fn dnf(&self) -> NormalForm<S, C> {
+        self.to_term().dnf()
+    }
+
+    /// Conjunctive normal form (see `Term::cnf`).
+    pub fn cnf(&self) -> NormalForm<S, C> {
+        self.to_term().cnf()
+    }
+}
@@ -1,7 +1,10 @@
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::marker::PhantomData;
 
-use super::Expr;
+use super::{
+    Expr, ExprBatch, ExprFormat, ExprFormatter, ExprTerm, ExprVisit, ExprWith,
+    Precedence, Term, Visitor
+};
 
 use crate::opset::Opset;
 use crate::value::Membership;
@@ -49,6 +52,39 @@ impl<S, V: Display> Display for ExprNot<S, V> {
     }
 }
 
+impl<S, V: ExprFormat> ExprFormat for ExprNot<S, V> {
+    fn precedence(&self) -> Precedence {
+        Precedence::Not
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        fmt.fmt_not(f, &self.val)
+    }
+}
+
+impl<S, V, C> ExprVisit<S, C> for ExprNot<S, V>
+where
+    S: Opset,
+    V: ExprVisit<S, C>,
+    C: ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        self.val.visit(ctx, visitor);
+        visitor.not();
+    }
+}
+
+impl<S, V, C> ExprTerm<S, C> for ExprNot<S, V>
+where
+    S: Opset,
+    V: ExprTerm<S, C>,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::Not(Box::new(self.val.to_term()))
+    }
+}
+
 impl<S, V> Expr<S> for ExprNot<S, V>
 where
     S: Opset,
@@ -60,6 +96,31 @@ where
     }
 }
 
+impl<S, V, C> ExprWith<S, C> for ExprNot<S, V>
+where
+    S: Opset,
+    V: ExprWith<S, C>,
+    C: ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        !self.val.eval_with(ctx)
+    }
+}
+
+impl<S, V, B> ExprBatch<S, B> for ExprNot<S, V>
+where
+    S: Opset,
+    V: ExprBatch<S, B>,
+    B: ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        self.val.eval_batch(cols, out);
+        S::not_slice(out);
+    }
+}
+
 impl_fuzzy_expr_ops! {
     ExprNot<S, V>
 }
@@ -0,0 +1,231 @@
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+
+use super::{
+    Expr, ExprBatch, ExprFormat, ExprFormatter, ExprTerm, ExprVisit, ExprWith,
+    Precedence, Term, Visitor
+};
+
+use crate::opset::Opset;
+use crate::value::Membership;
+use crate::impl_fuzzy_expr_ops;
+
+
+macro_rules! impl_one_of {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident { $($var:ident($e:ident, $ctor:ident) = $idx:literal),+ }
+    )*) => {$(
+        $(#[$meta])*
+        pub enum $name<S, $($e),+> {
+            $($var($e),)+
+            Never(!, PhantomData<S>)
+        }
+
+        impl<S, $($e),+> $name<S, $($e),+> {
+            $(
+                #[doc = concat!("Branch ", stringify!($idx), ".")]
+                pub fn $ctor(val: $e) -> Self {
+                    $name::$var(val)
+                }
+            )+
+
+            /// Builds the branch of the given index.
+            /// Panics if there is no such branch.
+            #[allow(clippy::too_many_arguments)]
+            pub fn select(
+                idx: usize,
+                $($ctor: impl FnOnce() -> $e),+
+            ) -> Self {
+                match idx {
+                    $($idx => $name::$var($ctor()),)+
+                    _ => panic!("Branch index out of range!")
+                }
+            }
+
+            /// Index of the branch.
+            pub fn index(&self) -> usize {
+                match self {
+                    $($name::$var(_) => $idx,)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, $($e: Clone),+> Clone for $name<S, $($e),+> {
+            fn clone(&self) -> Self {
+                match self {
+                    $($name::$var(e) => $name::$var(e.clone()),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, $($e: Debug),+> Debug for $name<S, $($e),+> {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                match self {
+                    $($name::$var(e) => write!(
+                        f,
+                        concat!(stringify!($name), "::", stringify!($var),
+                            "({:?})"),
+                        e
+                    ),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, $($e: Display),+> Display for $name<S, $($e),+> {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                match self {
+                    $($name::$var(e) => write!(f, "{}", e),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, $($e: ExprFormat),+> ExprFormat for $name<S, $($e),+> {
+            fn precedence(&self) -> Precedence {
+                match self {
+                    $($name::$var(e) => e.precedence(),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+
+            fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter)
+                -> FmtResult
+            {
+                match self {
+                    $($name::$var(e) => e.fmt_with(fmt, f),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, C, $($e),+> ExprVisit<S, C> for $name<S, $($e),+>
+        where
+            S: Opset,
+            C: ?Sized,
+            $($e: ExprVisit<S, C>),+
+        {
+            fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+                match self {
+                    $($name::$var(e) => e.visit(ctx, visitor),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, C, $($e),+> ExprTerm<S, C> for $name<S, $($e),+>
+        where
+            S: Opset,
+            C: ?Sized,
+            $($e: ExprTerm<S, C>),+
+        {
+            fn to_term(&self) -> Term<S, C> {
+                match self {
+                    $($name::$var(e) => e.to_term(),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S: Opset, $($e: Expr<S>),+> Expr<S> for $name<S, $($e),+> {
+            #[inline]
+            fn to_value(&self) -> Membership<S> {
+                match self {
+                    $($name::$var(e) => e.to_value(),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, C, $($e),+> ExprWith<S, C> for $name<S, $($e),+>
+        where
+            S: Opset,
+            C: ?Sized,
+            $($e: ExprWith<S, C>),+
+        {
+            #[inline]
+            fn eval_with(&self, ctx: &C) -> Membership<S> {
+                match self {
+                    $($name::$var(e) => e.eval_with(ctx),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl<S, B, $($e),+> ExprBatch<S, B> for $name<S, $($e),+>
+        where
+            S: Opset,
+            B: ?Sized,
+            $($e: ExprBatch<S, B>),+
+        {
+            #[inline]
+            fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+                match self {
+                    $($name::$var(e) => e.eval_batch(cols, out),)+
+                    $name::Never(..) => panic!("Invalid state!")
+                }
+            }
+        }
+
+        impl_fuzzy_expr_ops! {
+            $name<S, $($e),+>
+        }
+    )*};
+}
+
+impl_one_of! {
+    /// Fuzzy expression for runtime choice of one of 3 paths.
+    ///
+    /// Generalizes `ExprEither`, as do `ExprOneOf4` up to `ExprOneOf8`.
+    /// Branches can be built directly or selected by index:
+    ///
+    /// ```
+    /// # use fuzzy_systems::{Expr, ExprOneOf3, Hamacher1};
+    /// let a = Expr::<Hamacher1>::new(0.4).with_tag("a");
+    /// let b = Expr::new(0.6).with_tag("b");
+    ///
+    /// let shape = 2;
+    /// let d = ExprOneOf3::select(shape, || a | b, || a & b, || !a);
+    /// assert_eq!(d.index(), 2);
+    /// assert_eq!(d.to_string(), "!a");
+    ///
+    /// let e = d | b;
+    /// assert_eq!(e.to_string(), "(!a | b)");
+    /// assert_eq!(e.to_value(), (!a | b).to_value());
+    /// ```
+    ExprOneOf3 {
+        B0(E0, b0) = 0, B1(E1, b1) = 1, B2(E2, b2) = 2
+    }
+
+    /// Fuzzy expression for runtime choice of one of 4 paths.
+    ExprOneOf4 {
+        B0(E0, b0) = 0, B1(E1, b1) = 1, B2(E2, b2) = 2, B3(E3, b3) = 3
+    }
+
+    /// Fuzzy expression for runtime choice of one of 5 paths.
+    ExprOneOf5 {
+        B0(E0, b0) = 0, B1(E1, b1) = 1, B2(E2, b2) = 2, B3(E3, b3) = 3,
+        B4(E4, b4) = 4
+    }
+
+    /// Fuzzy expression for runtime choice of one of 6 paths.
+    ExprOneOf6 {
+        B0(E0, b0) = 0, B1(E1, b1) = 1, B2(E2, b2) = 2, B3(E3, b3) = 3,
+        B4(E4, b4) = 4, B5(E5, b5) = 5
+    }
+
+    /// Fuzzy expression for runtime choice of one of 7 paths.
+    ExprOneOf7 {
+        B0(E0, b0) = 0, B1(E1, b1) = 1, B2(E2, b2) = 2, B3(E3, b3) = 3,
+        B4(E4, b4) = 4, B5(E5, b5) = 5, B6(E6, b6) = 6
+    }
+
+    /// Fuzzy expression for runtime choice of one of 8 paths.
+    ExprOneOf8 {
+        B0(E0, b0) = 0, B1(E1, b1) = 1, B2(E2, b2) = 2, B3(E3, b3) = 3,
+        B4(E4, b4) = 4, B5(E5, b5) = 5, B6(E6, b6) = 6, B7(E7, b7) = 7
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Expression implementing neither `ExprFormat`, `ExprVisit` nor
+/// `ExprTerm`, held as an opaque leaf named `name`.
+///
+/// Lets `ExprDyn` wrap expressions of other crates, see
+/// `ExprPtr::opaque`. Displays and is visited as a variable named `name`,
+/// with its value if the context is given or not needed.
+pub struct ExprOpaque<S: Opset, C: ?Sized, E, N> {
+    expr: E,
+    name: N,
+    eval: fn(&E, Option<&C>) -> Option<Membership<S>>,
+    phantom: PhantomData<S>
+}
+
+impl<S: Opset, C: ?Sized, E, N> ExprOpaque<S, C, E, N> {
+    /// Creates from expression evaluable with context `C`.
+    pub fn new(name: N, expr: E) -> Self
+    where
+        E: ExprWith<S, C>
+    {
+        Self::with_eval(name, expr, |expr, ctx| {
+            ctx.map(|ctx| expr.eval_with(ctx))
+        })
+    }
+
+    /// Creates from expression without variables, which ignores
+    /// the context.
+    pub fn from_value(name: N, expr: E) -> Self
+    where
+        E: Expr<S>
+    {
+        Self::with_eval(name, expr, |expr, _ctx| Some(expr.to_value()))
+    }
+
+    fn with_eval(
+        name: N,
+        expr: E,
+        eval: fn(&E, Option<&C>) -> Option<Membership<S>>
+    ) -> Self {
+        Self {
+            expr,
+            name,
+            eval,
+            phantom: PhantomData
+        }
+    }
+
+    /// Wrapped expression.
+    pub fn expr(&self) -> &E {
+        &self.expr
+    }
+
+    /// Name of the leaf.
+    pub fn name(&self) -> &N {
+        &self.name
+    }
+}
+
+impl<S, C, E, N> Clone for ExprOpaque<S, C, E, N>
+where
+    S: Opset,
+    C: ?Sized,
+    E: Clone,
+    N: Clone
+{
+    fn clone(&self) -> Self {
+        Self::with_eval(self.name.clone(), self.expr.clone(), self.eval)
+    }
+}
+
+impl<S: Opset, C: ?Sized, E, N: Debug> Debug for ExprOpaque<S, C, E, N> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "ExprOpaque({:?})", self.name)
+    }
+}
+
+impl<S: Opset, C: ?Sized, E, N: Display> Display for ExprOpaque<S, C, E, N> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.fmt_with(&ExprFormatter::new(), f)
+    }
+}
+
+impl<S, C, E, N> ExprFormat for ExprOpaque<S, C, E, N>
+where
+    S: Opset,
+    C: ?Sized,
+    N: Display
+{
+    fn precedence(&self) -> Precedence {
+        Precedence::Atom
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        fmt.fmt_var(f, &self.name)
+    }
+}
+
+impl<S, C, E, N> ExprVisit<S, C> for ExprOpaque<S, C, E, N>
+where
+    S: Opset,
+    C: ?Sized,
+    N: Display
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        visitor.var(&self.name, (self.eval)(&self.expr, ctx));
+    }
+}
+
+impl<S, C, E, N> ExprTerm<S, C> for ExprOpaque<S, C, E, N>
+where
+    S: Opset + 'static,
+    C: ?Sized + 'static,
+    E: Clone + 'static,
+    N: Display + Debug + Clone + 'static
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::leaf(self.clone())
+    }
+}
+
+impl<S: Opset, E, N> Expr<S> for ExprOpaque<S, (), E, N> {
+    #[inline]
+    fn to_value(&self) -> Membership<S> {
+        self.eval_with(&())
+    }
+}
+
+impl<S: Opset, C: ?Sized, E, N> ExprWith<S, C> for ExprOpaque<S, C, E, N> {
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        (self.eval)(&self.expr, Some(ctx)).expect("Invalid state!")
+    }
+}
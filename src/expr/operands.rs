@@ -0,0 +1,229 @@
+use std::fmt::Result as FmtResult;
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Collection of operand expressions, as held by `ExprAll` and `ExprAny`.
+///
+/// Implemented for arrays, `Vec`s and tuples (up to 8 elements)
+/// of expressions. The `Operands*` traits give access to the operands
+/// as far as all of them support it.
+pub trait Operands {
+    /// Number of operands.
+    fn len(&self) -> usize;
+
+    /// Whether there are no operands.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Operands evaluable as `Expr`.
+pub trait OperandsValue<S: Opset>: Operands {
+    /// Calls `f` with each operand's value, in order.
+    fn each_value(&self, f: &mut dyn FnMut(Membership<S>));
+}
+
+/// Operands evaluable against context `C`.
+pub trait OperandsWith<S: Opset, C: ?Sized>: Operands {
+    /// Calls `f` with each operand's value for the given input, in order.
+    fn each_with(&self, ctx: &C, f: &mut dyn FnMut(Membership<S>));
+}
+
+/// Operands printable by `ExprFormatter`.
+pub trait OperandsFormat: Operands {
+    /// Calls `f` with each operand, in order, until it fails.
+    fn each_format(&self, f: &mut dyn FnMut(&dyn ExprFormat) -> FmtResult)
+        -> FmtResult;
+}
+
+/// Operands traversable by `Visitor`.
+pub trait OperandsVisit<S: Opset, C: ?Sized>: Operands {
+    /// Calls `f` with each operand, in order.
+    fn each_visit(&self, f: &mut dyn FnMut(&dyn ExprVisit<S, C>));
+}
+
+/// Operands evaluable over a batch of inputs.
+pub trait OperandsBatch<S: Opset, B: ?Sized>: Operands {
+    /// Calls `f` with each operand, in order.
+    fn each_batch(&self, f: &mut dyn FnMut(&dyn ExprBatch<S, B>));
+}
+
+/// Operands convertible to `Term`.
+pub trait OperandsTerm<S: Opset, C: ?Sized>: Operands {
+    /// Calls `f` with each operand's term, in order.
+    fn each_term(&self, f: &mut dyn FnMut(Term<S, C>));
+}
+
+
+macro_rules! impl_seq_operands {
+    ($( [$($gen:tt)*] $seq:ty ),*) => {$(
+        impl<E, $($gen)*> Operands for $seq {
+            fn len(&self) -> usize {
+                self.iter().len()
+            }
+        }
+
+        impl<S: Opset, E: Expr<S>, $($gen)*> OperandsValue<S> for $seq {
+            fn each_value(&self, f: &mut dyn FnMut(Membership<S>)) {
+                for expr in self.iter() {
+                    f(expr.to_value());
+                }
+            }
+        }
+
+        impl<S, C, E, $($gen)*> OperandsWith<S, C> for $seq
+        where
+            S: Opset,
+            C: ?Sized,
+            E: ExprWith<S, C>
+        {
+            fn each_with(&self, ctx: &C, f: &mut dyn FnMut(Membership<S>)) {
+                for expr in self.iter() {
+                    f(expr.eval_with(ctx));
+                }
+            }
+        }
+
+        impl<E: ExprFormat, $($gen)*> OperandsFormat for $seq {
+            fn each_format(
+                &self,
+                f: &mut dyn FnMut(&dyn ExprFormat) -> FmtResult
+            ) -> FmtResult {
+                for expr in self.iter() {
+                    f(expr)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl<S, C, E, $($gen)*> OperandsVisit<S, C> for $seq
+        where
+            S: Opset,
+            C: ?Sized,
+            E: ExprVisit<S, C>
+        {
+            fn each_visit(&self, f: &mut dyn FnMut(&dyn ExprVisit<S, C>)) {
+                for expr in self.iter() {
+                    f(expr);
+                }
+            }
+        }
+
+        impl<S, B, E, $($gen)*> OperandsBatch<S, B> for $seq
+        where
+            S: Opset,
+            B: ?Sized,
+            E: ExprBatch<S, B>
+        {
+            fn each_batch(&self, f: &mut dyn FnMut(&dyn ExprBatch<S, B>)) {
+                for expr in self.iter() {
+                    f(expr);
+                }
+            }
+        }
+
+        impl<S, C, E, $($gen)*> OperandsTerm<S, C> for $seq
+        where
+            S: Opset,
+            C: ?Sized,
+            E: ExprTerm<S, C>
+        {
+            fn each_term(&self, f: &mut dyn FnMut(Term<S, C>)) {
+                for expr in self.iter() {
+                    f(expr.to_term());
+                }
+            }
+        }
+    )*};
+}
+
+impl_seq_operands! {
+    [const N: usize] [E; N],
+    [] Vec<E>
+}
+
+
+macro_rules! impl_tuple_operands {
+    ($( ($($n:tt: $e:ident),+) )*) => {$(
+        impl<$($e),+> Operands for ($($e,)+) {
+            fn len(&self) -> usize {
+                [$($n),+].len()
+            }
+        }
+
+        impl<S: Opset, $($e: Expr<S>),+> OperandsValue<S> for ($($e,)+) {
+            fn each_value(&self, f: &mut dyn FnMut(Membership<S>)) {
+                $( f(self.$n.to_value()); )+
+            }
+        }
+
+        impl<S, C, $($e),+> OperandsWith<S, C> for ($($e,)+)
+        where
+            S: Opset,
+            C: ?Sized,
+            $($e: ExprWith<S, C>),+
+        {
+            fn each_with(&self, ctx: &C, f: &mut dyn FnMut(Membership<S>)) {
+                $( f(self.$n.eval_with(ctx)); )+
+            }
+        }
+
+        impl<$($e: ExprFormat),+> OperandsFormat for ($($e,)+) {
+            fn each_format(
+                &self,
+                f: &mut dyn FnMut(&dyn ExprFormat) -> FmtResult
+            ) -> FmtResult {
+                $( f(&self.$n)?; )+
+                Ok(())
+            }
+        }
+
+        impl<S, C, $($e),+> OperandsVisit<S, C> for ($($e,)+)
+        where
+            S: Opset,
+            C: ?Sized,
+            $($e: ExprVisit<S, C>),+
+        {
+            fn each_visit(&self, f: &mut dyn FnMut(&dyn ExprVisit<S, C>)) {
+                $( f(&self.$n); )+
+            }
+        }
+
+        impl<S, B, $($e),+> OperandsBatch<S, B> for ($($e,)+)
+        where
+            S: Opset,
+            B: ?Sized,
+            $($e: ExprBatch<S, B>),+
+        {
+            fn each_batch(&self, f: &mut dyn FnMut(&dyn ExprBatch<S, B>)) {
+                $( f(&self.$n); )+
+            }
+        }
+
+        impl<S, C, $($e),+> OperandsTerm<S, C> for ($($e,)+)
+        where
+            S: Opset,
+            C: ?Sized,
+            $($e: ExprTerm<S, C>),+
+        {
+            fn each_term(&self, f: &mut dyn FnMut(Term<S, C>)) {
+                $( f(self.$n.to_term()); )+
+            }
+        }
+    )*};
+}
+
+impl_tuple_operands! {
+    (0: E0)
+    (0: E0, 1: E1)
+    (0: E0, 1: E1, 2: E2)
+    (0: E0, 1: E1, 2: E2, 3: E3)
+    (0: E0, 1: E1, 2: E2, 3: E3, 4: E4)
+    (0: E0, 1: E1, 2: E2, 3: E3, 4: E4, 5: E5)
+    (0: E0, 1: E1, 2: E2, 3: E3, 4: E4, 5: E5, 6: E6)
+    (0: E0, 1: E1, 2: E2, 3: E3, 4: E4, 5: E5, 6: E6, 7: E7)
+}
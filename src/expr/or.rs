@@ -1,7 +1,11 @@
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::marker::PhantomData;
 
-use super::Expr;
+use super::{
+    Expr, ExprBatch, ExprFormat, ExprFormatter, ExprTerm, ExprVisit, ExprWith,
+    Precedence, Term, Visitor
+};
+use super::batch::scratch;
 
 use crate::opset::Opset;
 use crate::value::Membership;
@@ -53,6 +57,46 @@ impl<S, L: Display, R: Display> Display for ExprOr<S, L, R> {
     }
 }
 
+impl<S, L, R> ExprFormat for ExprOr<S, L, R>
+where
+    L: ExprFormat,
+    R: ExprFormat
+{
+    fn precedence(&self) -> Precedence {
+        Precedence::Or
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        fmt.fmt_or(f, &self.lhs, &self.rhs)
+    }
+}
+
+impl<S, L, R, C> ExprVisit<S, C> for ExprOr<S, L, R>
+where
+    S: Opset,
+    L: ExprVisit<S, C>,
+    R: ExprVisit<S, C>,
+    C: ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        self.lhs.visit(ctx, visitor);
+        self.rhs.visit(ctx, visitor);
+        visitor.or();
+    }
+}
+
+impl<S, L, R, C> ExprTerm<S, C> for ExprOr<S, L, R>
+where
+    S: Opset,
+    L: ExprTerm<S, C>,
+    R: ExprTerm<S, C>,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::Or(Box::new(self.lhs.to_term()), Box::new(self.rhs.to_term()))
+    }
+}
+
 impl<S, L, R> Expr<S> for ExprOr<S, L, R>
 where
     S: Opset,
@@ -67,6 +111,37 @@ where
     }
 }
 
+impl<S, L, R, C> ExprWith<S, C> for ExprOr<S, L, R>
+where
+    S: Opset,
+    L: ExprWith<S, C>,
+    R: ExprWith<S, C>,
+    C: ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        let lhs = self.lhs.eval_with(ctx);
+        let rhs = self.rhs.eval_with(ctx);
+        lhs | rhs
+    }
+}
+
+impl<S, L, R, B> ExprBatch<S, B> for ExprOr<S, L, R>
+where
+    S: Opset,
+    L: ExprBatch<S, B>,
+    R: ExprBatch<S, B>,
+    B: ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        let mut rhs = scratch(out.len());
+        self.lhs.eval_batch(cols, out);
+        self.rhs.eval_batch(cols, &mut rhs);
+        S::or_slice(out, &rhs);
+    }
+}
+
 impl_fuzzy_expr_ops! {
     ExprOr<S, L, R>
 }
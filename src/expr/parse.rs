@@ -0,0 +1,452 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::hash::{BuildHasher, Hash};
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::{Membership, Raw, Value};
+
+
+/// Resolver of identifiers in parsed expressions.
+///
+/// Implemented by:
+///  * maps of values (e.g. `HashMap<String, Membership<S>>`), resolving
+///    into tagged values
+///  * sets of names (e.g. `HashSet<String>`), resolving into `String`
+///    variables, to be looked up in a map context on evaluation
+///  * closures `Fn(&str) -> Option<ExprDyn<S, C>>`
+pub trait Resolve<S: Opset, C: ?Sized> {
+    /// Expression the identifier stands for, if any.
+    fn resolve(&self, ident: &str) -> Option<ExprDyn<S, C>>;
+}
+
+impl<S, C, F> Resolve<S, C> for F
+where
+    S: Opset,
+    C: ?Sized,
+    F: Fn(&str) -> Option<ExprDyn<S, C>>
+{
+    fn resolve(&self, ident: &str) -> Option<ExprDyn<S, C>> {
+        self(ident)
+    }
+}
+
+impl<S, C, K, V, H> Resolve<S, C> for HashMap<K, V, H>
+where
+    S: Opset + 'static,
+    C: ?Sized + 'static,
+    K: Borrow<str> + Hash + Eq,
+    V: Value<S>,
+    H: BuildHasher
+{
+    fn resolve(&self, ident: &str) -> Option<ExprDyn<S, C>> {
+        self.get(ident).map(|val| resolve_value(ident, val))
+    }
+}
+
+impl<S, C, K, V> Resolve<S, C> for BTreeMap<K, V>
+where
+    S: Opset + 'static,
+    C: ?Sized + 'static,
+    K: Borrow<str> + Ord,
+    V: Value<S>
+{
+    fn resolve(&self, ident: &str) -> Option<ExprDyn<S, C>> {
+        self.get(ident).map(|val| resolve_value(ident, val))
+    }
+}
+
+impl<S, C, K, H> Resolve<S, C> for HashSet<K, H>
+where
+    S: Opset + 'static,
+    C: Context<S, String> + ?Sized + 'static,
+    K: Borrow<str> + Hash + Eq,
+    H: BuildHasher
+{
+    fn resolve(&self, ident: &str) -> Option<ExprDyn<S, C>> {
+        self.get(ident).map(|_| resolve_var(ident))
+    }
+}
+
+impl<S, C, K> Resolve<S, C> for BTreeSet<K>
+where
+    S: Opset + 'static,
+    C: Context<S, String> + ?Sized + 'static,
+    K: Borrow<str> + Ord
+{
+    fn resolve(&self, ident: &str) -> Option<ExprDyn<S, C>> {
+        self.get(ident).map(|_| resolve_var(ident))
+    }
+}
+
+fn resolve_value<S, C, V>(ident: &str, val: &V) -> ExprDyn<S, C>
+where
+    S: Opset + 'static,
+    C: ?Sized + 'static,
+    V: Value<S>
+{
+    let tag = ident.to_string();
+    ExprDyn::new(ExprTagged::from_membership(val.membership(), tag))
+}
+
+fn resolve_var<S, C>(ident: &str) -> ExprDyn<S, C>
+where
+    S: Opset + 'static,
+    C: Context<S, String> + ?Sized + 'static
+{
+    ExprDyn::new(ExprVar::new(ident.to_string()))
+}
+
+
+/// Kind of expression parsing error.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseErrorKind {
+    /// Character not allowed in expressions.
+    InvalidChar(char),
+    /// Numeric literal which is not a valid membership.
+    InvalidNumber(String),
+    /// Identifier not known to the resolver.
+    UnknownIdent(String),
+    /// Other token (or end of input) than the expected one.
+    Expected(&'static str),
+    /// Parentheses or negations nested more than 256 levels deep,
+    /// or more than 1024 binary operators in total.
+    TooDeep
+}
+
+/// Expression parsing error.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    pos: usize
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, pos: usize) -> Self {
+        Self {
+            kind,
+            pos
+        }
+    }
+
+    /// Kind of the error.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// Byte offset of the error in the parsed string.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match &self.kind {
+            ParseErrorKind::InvalidChar(c) =>
+                write!(f, "invalid character {:?}", c)?,
+            ParseErrorKind::InvalidNumber(num) =>
+                write!(f, "invalid membership {}", num)?,
+            ParseErrorKind::UnknownIdent(ident) =>
+                write!(f, "unknown identifier {}", ident)?,
+            ParseErrorKind::Expected(what) =>
+                write!(f, "expected {}", what)?,
+            ParseErrorKind::TooDeep =>
+                write!(
+                    f,
+                    "nesting deeper than {} or more than {} operators",
+                    MAX_DEPTH,
+                    MAX_OPERATORS
+                )?
+        }
+        write!(f, " at position {}", self.pos)
+    }
+}
+
+impl Error for ParseError {}
+
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token<'a> {
+    Ident(&'a str),
+    Number(&'a str),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+    End
+}
+
+/// Splits the source into tokens, along with their positions.
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0
+        }
+    }
+
+    fn next(&mut self) -> Result<(Token<'a>, usize), ParseError> {
+        let rest = &self.src[self.pos..];
+        let trimmed = rest.trim_start();
+        let start = self.pos + (rest.len() - trimmed.len());
+
+        let mut chars = trimmed.chars();
+        let (token, len) = match chars.next() {
+            None => (Token::End, 0),
+            Some('!') => (Token::Not, 1),
+            Some('&') => (Token::And, 1),
+            Some('|') => (Token::Or, 1),
+            Some('(') => (Token::Open, 1),
+            Some(')') => (Token::Close, 1),
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let len = Self::span(trimmed, |c| {
+                    c.is_ascii_digit() || c == '.'
+                });
+                (Token::Number(&trimmed[..len]), len)
+            },
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let len = Self::span(trimmed, |c| {
+                    c.is_alphanumeric() || c == '_'
+                });
+                (Token::Ident(&trimmed[..len]), len)
+            },
+            Some(c) => {
+                let kind = ParseErrorKind::InvalidChar(c);
+                return Err(ParseError::new(kind, start));
+            }
+        };
+
+        self.pos = start + len;
+        Ok((token, start))
+    }
+
+    fn span<F: Fn(char) -> bool>(src: &str, pred: F) -> usize {
+        src.find(|c| !pred(c)).unwrap_or(src.len())
+    }
+}
+
+/// Maximum nesting of parentheses and negations in parsed expressions.
+const MAX_DEPTH: usize = 256;
+
+/// Maximum number of binary operators in parsed expressions.
+const MAX_OPERATORS: usize = 1024;
+
+/// Recursive descent parser, with `!` binding stronger than `&`
+/// and `&` binding stronger than `|`.
+///
+/// Recursion is bounded by `MAX_DEPTH` and chains of operators, which
+/// build left-deep trees, by `MAX_OPERATORS`. So trees are at most
+/// `MAX_DEPTH + MAX_OPERATORS` levels deep, and malicious input fails
+/// instead of overflowing the stack on parsing, evaluation or drop.
+struct Parser<'a, 'r, R> {
+    lexer: Lexer<'a>,
+    current: (Token<'a>, usize),
+    resolver: &'r R,
+    depth: usize,
+    operators: usize
+}
+
+impl<'a, 'r, R> Parser<'a, 'r, R> {
+    fn new(src: &'a str, resolver: &'r R) -> Result<Self, ParseError> {
+        let mut lexer = Lexer::new(src);
+        let current = lexer.next()?;
+        Ok(Self {
+            lexer,
+            current,
+            resolver,
+            depth: 0,
+            operators: 0
+        })
+    }
+
+    /// Runs `f` one nesting level deeper, failing at `pos` if too deep.
+    fn nested<T, F>(&mut self, pos: usize, f: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ParseError>
+    {
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError::new(ParseErrorKind::TooDeep, pos));
+        }
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        res
+    }
+
+    /// Consumes binary operator, failing if there are too many.
+    fn operator(&mut self) -> Result<(), ParseError> {
+        let (_, pos) = self.advance()?;
+        if self.operators == MAX_OPERATORS {
+            return Err(ParseError::new(ParseErrorKind::TooDeep, pos));
+        }
+        self.operators += 1;
+        Ok(())
+    }
+
+    fn advance(&mut self) -> Result<(Token<'a>, usize), ParseError> {
+        let next = self.lexer.next()?;
+        Ok(std::mem::replace(&mut self.current, next))
+    }
+
+    fn expect(&mut self, token: Token, what: &'static str)
+        -> Result<(), ParseError> {
+
+        if self.current.0 == token {
+            self.advance()?;
+            Ok(())
+        }
+        else {
+            let kind = ParseErrorKind::Expected(what);
+            Err(ParseError::new(kind, self.current.1))
+        }
+    }
+
+    fn parse<S, C>(&mut self) -> Result<ExprDyn<S, C>, ParseError>
+    where
+        S: Opset + 'static,
+        C: ?Sized + 'static,
+        R: Resolve<S, C>
+    {
+        let expr = self.parse_or()?;
+        self.expect(Token::End, "operator")?;
+        Ok(expr)
+    }
+
+    fn parse_or<S, C>(&mut self) -> Result<ExprDyn<S, C>, ParseError>
+    where
+        S: Opset + 'static,
+        C: ?Sized + 'static,
+        R: Resolve<S, C>
+    {
+        let mut expr = self.parse_and()?;
+        while self.current.0 == Token::Or {
+            self.operator()?;
+            expr = expr | self.parse_and()?;
+        }
+        Ok(expr)
+    }
+
+    fn parse_and<S, C>(&mut self) -> Result<ExprDyn<S, C>, ParseError>
+    where
+        S: Opset + 'static,
+        C: ?Sized + 'static,
+        R: Resolve<S, C>
+    {
+        let mut expr = self.parse_not()?;
+        while self.current.0 == Token::And {
+            self.operator()?;
+            expr = expr & self.parse_not()?;
+        }
+        Ok(expr)
+    }
+
+    fn parse_not<S, C>(&mut self) -> Result<ExprDyn<S, C>, ParseError>
+    where
+        S: Opset + 'static,
+        C: ?Sized + 'static,
+        R: Resolve<S, C>
+    {
+        if self.current.0 == Token::Not {
+            let (_, pos) = self.advance()?;
+            Ok(!self.nested(pos, Self::parse_not)?)
+        }
+        else {
+            self.parse_operand()
+        }
+    }
+
+    fn parse_operand<S, C>(&mut self) -> Result<ExprDyn<S, C>, ParseError>
+    where
+        S: Opset + 'static,
+        C: ?Sized + 'static,
+        R: Resolve<S, C>
+    {
+        match self.current.clone() {
+            (Token::Open, pos) => {
+                self.advance()?;
+                let expr = self.nested(pos, Self::parse_or)?;
+                self.expect(Token::Close, "`)`")?;
+                Ok(expr)
+            },
+            (Token::Number(num), pos) => {
+                self.advance()?;
+                num.parse::<Raw>()
+                    .ok()
+                    .and_then(Membership::try_new)
+                    .map(|val| ExprDyn::new(ExprValue::from_membership(val)))
+                    .ok_or_else(|| {
+                        let kind = ParseErrorKind::InvalidNumber(num.into());
+                        ParseError::new(kind, pos)
+                    })
+            },
+            (Token::Ident(ident), pos) => {
+                self.advance()?;
+                self.resolver.resolve(ident).ok_or_else(|| {
+                    let kind = ParseErrorKind::UnknownIdent(ident.into());
+                    ParseError::new(kind, pos)
+                })
+            },
+            (_, pos) => {
+                let kind = ParseErrorKind::Expected("operand");
+                Err(ParseError::new(kind, pos))
+            }
+        }
+    }
+}
+
+impl<S: Opset + 'static, C: ?Sized + 'static> ExprDyn<S, C> {
+    /// Parses expression in the syntax produced by `Display`,
+    /// e.g. `((a | b) & !0.4)`, resolving identifiers via `resolver`.
+    /// Parentheses and negations nest at most 256 levels deep and there
+    /// are at most 1024 binary operators, other input fails with
+    /// `ParseErrorKind::TooDeep`.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use fuzzy_systems::{Expr, ExprDyn, Hamacher1, Opset};
+    /// let mut memberships = HashMap::new();
+    /// memberships.insert("a", Hamacher1::member(0.1));
+    /// memberships.insert("b", Hamacher1::member(0.6));
+    ///
+    /// let d = ExprDyn::parse("(a | b) & !0.4", &memberships).unwrap();
+    /// assert_eq!(d.to_string(), "((a | b) & !0.4)");
+    /// assert!((d.to_value().as_raw() - 0.384).abs() < 0.0001);
+    ///
+    /// let e = ExprDyn::<Hamacher1>::parse("a & (b |", &memberships);
+    /// assert_eq!(e.unwrap_err().to_string(), "expected operand at position 8");
+    /// ```
+    ///
+    /// Variables are resolved by a set of names and looked up
+    /// in a map on evaluation:
+    ///
+    /// ```
+    /// # use std::collections::{HashMap, HashSet};
+    /// # use fuzzy_systems::{ExprDyn, ExprWith, Membership, YagerInf};
+    /// type Input = HashMap<String, Membership<YagerInf>>;
+    ///
+    /// let vars: HashSet<_> = vec!["hot", "humid"].into_iter().collect();
+    /// let rule = ExprDyn::<YagerInf, Input>::parse("hot & !humid", &vars);
+    /// let rule = rule.unwrap();
+    ///
+    /// let mut input = Input::new();
+    /// input.insert("hot".into(), Membership::new(0.8));
+    /// input.insert("humid".into(), Membership::new(0.3));
+    /// assert_eq!(rule.eval_with(&input).as_raw(), 0.7);
+    /// ```
+    pub fn parse<R>(src: &str, resolver: &R) -> Result<Self, ParseError>
+    where
+        R: Resolve<S, C>
+    {
+        Parser::new(src, resolver)?.parse()
+    }
+}
@@ -0,0 +1,283 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::rc::Rc;
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+use crate::impl_fuzzy_expr_ops;
+
+
+thread_local! {
+    /// Last memoised evaluation started on the thread.
+    static LAST_SCOPE: Cell<u64> = const { Cell::new(0) };
+
+    /// Innermost memoised evaluation running on the thread, `0` if none.
+    static CURRENT_SCOPE: Cell<u64> = const { Cell::new(0) };
+}
+
+
+/// Memoised evaluation of `ExprShared::cached` expressions.
+///
+/// Each cached expression is evaluated at most once per `Memo::eval`
+/// or `Memo::eval_batch`, as the input stays the same throughout.
+/// Plain evaluation (`eval_with`, `eval_batch`) evaluates cached
+/// expressions every time instead.
+pub struct Memo {
+    prev: u64
+}
+
+impl Memo {
+    /// Evaluates expression for the input, each cached expression once.
+    pub fn eval<S, E, C>(expr: &E, ctx: &C) -> Membership<S>
+    where
+        S: Opset,
+        E: ExprWith<S, C> + ?Sized,
+        C: ?Sized
+    {
+        let _memo = Memo::enter();
+        expr.eval_with(ctx)
+    }
+
+    /// Evaluates expression for rows of the input columns as `eval_batch`
+    /// does, each cached expression once.
+    pub fn eval_batch<S, E, B>(expr: &E, cols: &B, out: &mut [Membership<S>])
+    where
+        S: Opset,
+        E: ExprBatch<S, B> + ?Sized,
+        B: ?Sized
+    {
+        let _memo = Memo::enter();
+        expr.eval_batch(cols, out)
+    }
+
+    fn enter() -> Self {
+        let scope = LAST_SCOPE.with(|last| {
+            last.set(last.get() + 1);
+            last.get()
+        });
+        Memo {
+            prev: CURRENT_SCOPE.with(|current| current.replace(scope))
+        }
+    }
+
+    fn current() -> u64 {
+        CURRENT_SCOPE.with(Cell::get)
+    }
+}
+
+impl Drop for Memo {
+    fn drop(&mut self) {
+        CURRENT_SCOPE.with(|current| current.set(self.prev));
+    }
+}
+
+
+/// Shared node of `ExprShared`.
+struct Shared<S: Opset, E> {
+    expr: E,
+    cached: bool,
+    /// Value without context, which never changes.
+    value: Cell<Option<Membership<S>>>,
+    /// Value within memoised evaluation.
+    scoped: Cell<Option<(u64, Membership<S>)>>,
+    /// Values of rows within memoised batch evaluation.
+    column: RefCell<Option<(u64, Vec<Membership<S>>)>>
+}
+
+
+/// Fuzzy expression shared between several places of a tree.
+///
+/// Cloning shares the expression instead of copying it. Shared
+/// expressions created via `cached` are also evaluated only once:
+/// for good when evaluated without context, once per `Memo::eval`
+/// or `Memo::eval_batch` otherwise:
+///
+/// ```
+/// # use fuzzy_systems::{Expr, ExprShared, ExprWith, Hamacher1, Memo};
+/// # use fuzzy_systems::Membership;
+/// let x = Expr::<Hamacher1>::var(0);
+/// let y = Expr::var(1);
+/// let c = ExprShared::cached(x | y);
+/// let d = (c.clone() & Expr::var(2)) | (c & !Expr::var(2));
+/// assert_eq!(d.to_string(), "(((0 | 1) & 2) | ((0 | 1) & !2))");
+///
+/// for input in [[0.1, 0.2, 0.3], [0.6, 0.5, 0.4]] {
+///     let input = input.map(Membership::new);
+///     // `x | y` evaluated once per input
+///     let value = Memo::eval(&d, &input);
+///     assert_eq!(value, d.eval_with(&input));
+/// }
+/// ```
+pub struct ExprShared<S: Opset, E> {
+    node: Rc<Shared<S, E>>
+}
+
+impl<S: Opset, E> ExprShared<S, E> {
+    /// Shares expression, evaluated wherever it is used.
+    pub fn new(expr: E) -> Self {
+        Self::with_caching(expr, false)
+    }
+
+    /// Shares expression, evaluated once as described above.
+    pub fn cached(expr: E) -> Self {
+        Self::with_caching(expr, true)
+    }
+
+    fn with_caching(expr: E, cached: bool) -> Self {
+        Self {
+            node: Rc::new(Shared {
+                expr,
+                cached,
+                value: Cell::new(None),
+                scoped: Cell::new(None),
+                column: RefCell::new(None)
+            })
+        }
+    }
+
+    /// Shared expression.
+    pub fn expr(&self) -> &E {
+        &self.node.expr
+    }
+
+    /// Whether the expression's value is cached.
+    pub fn is_cached(&self) -> bool {
+        self.node.cached
+    }
+
+    /// Whether both share the same expression.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.node, &other.node)
+    }
+}
+
+impl<S: Opset, E> Clone for ExprShared<S, E> {
+    fn clone(&self) -> Self {
+        Self {
+            node: Rc::clone(&self.node)
+        }
+    }
+}
+
+impl<S: Opset, E: Debug> Debug for ExprShared<S, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "ExprShared({:?})", self.node.expr)
+    }
+}
+
+impl<S: Opset, E: Display> Display for ExprShared<S, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.node.expr)
+    }
+}
+
+impl<S: Opset, E: ExprFormat> ExprFormat for ExprShared<S, E> {
+    fn precedence(&self) -> Precedence {
+        self.node.expr.precedence()
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        self.node.expr.fmt_with(fmt, f)
+    }
+}
+
+impl<S, E, C> ExprVisit<S, C> for ExprShared<S, E>
+where
+    S: Opset,
+    E: ExprVisit<S, C>,
+    C: ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        self.node.expr.visit(ctx, visitor)
+    }
+}
+
+impl<S, E, C> ExprTerm<S, C> for ExprShared<S, E>
+where
+    S: Opset,
+    E: ExprTerm<S, C>,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        self.node.expr.to_term()
+    }
+}
+
+impl<S, E> Expr<S> for ExprShared<S, E>
+where
+    S: Opset,
+    E: Expr<S>
+{
+    #[inline]
+    fn to_value(&self) -> Membership<S> {
+        let node = &*self.node;
+        if !node.cached {
+            return node.expr.to_value();
+        }
+        node.value.get().unwrap_or_else(|| {
+            let value = node.expr.to_value();
+            node.value.set(Some(value));
+            value
+        })
+    }
+}
+
+impl<S, E, C> ExprWith<S, C> for ExprShared<S, E>
+where
+    S: Opset,
+    E: ExprWith<S, C>,
+    C: ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        let node = &*self.node;
+        let scope = Memo::current();
+        if !node.cached || scope == 0 {
+            return node.expr.eval_with(ctx);
+        }
+
+        match node.scoped.get() {
+            Some((s, value)) if s == scope => value,
+            _ => {
+                let value = node.expr.eval_with(ctx);
+                node.scoped.set(Some((scope, value)));
+                value
+            }
+        }
+    }
+}
+
+impl<S, E, B> ExprBatch<S, B> for ExprShared<S, E>
+where
+    S: Opset,
+    E: ExprBatch<S, B>,
+    B: ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        let node = &*self.node;
+        let scope = Memo::current();
+        if !node.cached || scope == 0 {
+            return node.expr.eval_batch(cols, out);
+        }
+
+        let column = node.column.borrow();
+        match &*column {
+            Some((s, vals)) if *s == scope && vals.len() == out.len() => {
+                out.copy_from_slice(vals)
+            },
+            _ => {
+                // not borrowed while evaluating, in case of nesting
+                drop(column);
+                node.expr.eval_batch(cols, out);
+                *node.column.borrow_mut() = Some((scope, out.to_vec()));
+            }
+        }
+    }
+}
+
+impl_fuzzy_expr_ops! {
+    ExprShared<S, E>
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+use crate::opset::OpsetLaws;
+use crate::value::Membership;
+
+
+impl<S: OpsetLaws, C: ?Sized> Term<S, C> {
+    /// Rewrites into an equivalent term of at most the same size.
+    ///
+    /// Constants are folded, other rewrites are applied only if the
+    /// opset satisfies the required laws (see `OpsetLaws`):
+    ///
+    /// * `!!a` into `a` if involutive,
+    /// * `a & a` and `a | a` into `a` if idempotent,
+    /// * `a & 1`, `a & 0`, `a | 0`, `a | 1` if bounded,
+    /// * `!a & !b` into `!(a | b)`, `!(!a & b)` into `a | !b` (and duals)
+    ///   if De Morgan's laws hold, as long as the term gets smaller.
+    pub fn simplify(self) -> Self {
+        match self {
+            Term::Not(val) => Self::not(val.simplify(), true),
+            Term::And(lhs, rhs) => Self::and(lhs.simplify(), rhs.simplify()),
+            Term::Or(lhs, rhs) => Self::or(lhs.simplify(), rhs.simplify()),
+            term => term
+        }
+    }
+
+    /// Negation of simplified term, pushed down if it gets smaller.
+    fn not(val: Self, push: bool) -> Self {
+        match val {
+            Term::Const(val) => Term::Const(S::not(val)),
+            Term::Not(val) if S::INVOLUTIVE => *val,
+            Term::And(lhs, rhs) if push && S::DE_MORGAN => {
+                let pushed = Self::or(
+                    Self::not((*lhs).clone(), true),
+                    Self::not((*rhs).clone(), true)
+                );
+                let val = Term::And(lhs, rhs);
+                Self::smaller(Term::Not(Box::new(val)), pushed)
+            },
+            Term::Or(lhs, rhs) if push && S::DE_MORGAN => {
+                let pushed = Self::and(
+                    Self::not((*lhs).clone(), true),
+                    Self::not((*rhs).clone(), true)
+                );
+                let val = Term::Or(lhs, rhs);
+                Self::smaller(Term::Not(Box::new(val)), pushed)
+            },
+            val => Term::Not(Box::new(val))
+        }
+    }
+
+    /// Conjunction of simplified terms.
+    fn and(lhs: Self, rhs: Self) -> Self {
+        match (lhs, rhs) {
+            (Term::Const(lhs), Term::Const(rhs)) => {
+                Term::Const(S::and(lhs, rhs))
+            },
+            (Term::Const(val), term) | (term, Term::Const(val))
+                if Self::bound(val) =>
+            {
+                if val.as_raw() == 0.0 { Term::Const(val) } else { term }
+            },
+            (lhs, rhs) if S::IDEMPOTENT && lhs.same(&rhs) => lhs,
+            (Term::Not(lhs), Term::Not(rhs)) if S::DE_MORGAN => {
+                // never pushed back, as it's always smaller
+                Self::not(Self::or(*lhs, *rhs), false)
+            },
+            (lhs, rhs) => Term::And(Box::new(lhs), Box::new(rhs))
+        }
+    }
+
+    /// Alternative of simplified terms.
+    fn or(lhs: Self, rhs: Self) -> Self {
+        match (lhs, rhs) {
+            (Term::Const(lhs), Term::Const(rhs)) => {
+                Term::Const(S::or(lhs, rhs))
+            },
+            (Term::Const(val), term) | (term, Term::Const(val))
+                if Self::bound(val) =>
+            {
+                if val.as_raw() == 1.0 { Term::Const(val) } else { term }
+            },
+            (lhs, rhs) if S::IDEMPOTENT && lhs.same(&rhs) => lhs,
+            (Term::Not(lhs), Term::Not(rhs)) if S::DE_MORGAN => {
+                Self::not(Self::and(*lhs, *rhs), false)
+            },
+            (lhs, rhs) => Term::Or(Box::new(lhs), Box::new(rhs))
+        }
+    }
+
+    /// Whether the value is 0 or 1 and bounded by the opset.
+    fn bound(val: Membership<S>) -> bool {
+        S::BOUNDED && (val.as_raw() == 0.0 || val.as_raw() == 1.0)
+    }
+
+    fn smaller(term: Self, other: Self) -> Self {
+        if other.size() < term.size() { other } else { term }
+    }
+}
+
+impl<S: OpsetLaws + 'static, C: ?Sized + 'static> ExprDyn<S, C> {
+    /// Equivalent, possibly smaller expression (see `Term::simplify`).
+    ///
+    /// ```
+    /// # use fuzzy_systems::{Expr, ExprDyn, Hamacher1, YagerInf};
+    /// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+    /// let b = Expr::new(0.6).with_tag("b");
+    /// let d = ExprDyn::<Hamacher1>::new(!(!a & !b) & Expr::new(1.0));
+    /// assert_eq!(d.simplify().to_string(), "(a | b)");
+    ///
+    /// // idempotence holds only for YagerInf
+    /// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+    /// let d = ExprDyn::<Hamacher1>::new(a & a);
+    /// assert_eq!(d.simplify().to_string(), "(a & a)");
+    ///
+    /// let a = Expr::<YagerInf>::new(0.1).with_tag("a");
+    /// let d = ExprDyn::<YagerInf>::new(a & a);
+    /// assert_eq!(d.simplify().to_string(), "a");
+    /// ```
+    pub fn simplify(&self) -> Self {
+        self.to_term().simplify().to_expr()
+    }
+}
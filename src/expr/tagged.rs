@@ -1,11 +1,12 @@
+use std::any::Any;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::marker::PhantomData;
-use std::ops::{Not, BitAnd, BitOr};
 
 use super::*;
 
 use crate::opset::Opset;
 use crate::value::Membership;
+use crate::impl_fuzzy_expr_ops;
 
 
 /// Fuzzy value expression with custom tag.
@@ -59,6 +60,39 @@ impl<S: Opset, T: Display> Display for ExprTagged<S, T> {
     }
 }
 
+impl<S: Opset, T: Display> ExprFormat for ExprTagged<S, T> {
+    fn precedence(&self) -> Precedence {
+        Precedence::Atom
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        fmt.fmt_tag(f, &self.tag, &self.membership)
+    }
+}
+
+impl<S: Opset, T: Display, C: ?Sized> ExprVisit<S, C> for ExprTagged<S, T> {
+    fn visit(&self, _ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        visitor.tagged(&self.tag, self.membership);
+    }
+}
+
+impl<S, T, C> ExprTerm<S, C> for ExprTagged<S, T>
+where
+    S: Opset + 'static,
+    T: Display + Debug + Clone + PartialEq + 'static,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::leaf(self.clone())
+    }
+
+    fn same_leaf(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.membership == other.membership && self.tag == other.tag
+        })
+    }
+}
+
 impl<S: Opset, T> Expr<S> for ExprTagged<S, T> {
     #[inline]
     fn to_value(&self) -> Membership<S> {
@@ -66,37 +100,20 @@ impl<S: Opset, T> Expr<S> for ExprTagged<S, T> {
     }
 }
 
-impl<S, T> Not for ExprTagged<S, T>
-where
-    S: Opset,
-    Self: Sized
-{
-    type Output = ExprNot<S, Self>;
-    fn not(self) -> Self::Output {
-        Expr::not(self)
+impl<S: Opset, T, C: ?Sized> ExprWith<S, C> for ExprTagged<S, T> {
+    #[inline]
+    fn eval_with(&self, _ctx: &C) -> Membership<S> {
+        self.membership
     }
 }
 
-impl<S, T, R> BitAnd<R> for ExprTagged<S, T>
-where
-    S: Opset,
-    R: Expr<S>,
-    Self: Sized
-{
-    type Output = ExprAnd<S, Self, R>;
-    fn bitand(self, rhs: R) -> Self::Output {
-        Expr::and(self, rhs)
+impl<S: Opset, T, B: ?Sized> ExprBatch<S, B> for ExprTagged<S, T> {
+    #[inline]
+    fn eval_batch(&self, _cols: &B, out: &mut [Membership<S>]) {
+        out.fill(self.membership);
     }
 }
 
-impl<S, T, R> BitOr<R> for ExprTagged<S, T>
-where
-    S: Opset,
-    R: Expr<S>,
-    Self: Sized
-{
-    type Output = ExprOr<S, Self, R>;
-    fn bitor(self, rhs: R) -> Self::Output {
-        Expr::or(self, rhs)
-    }
+impl_fuzzy_expr_ops! {
+    ExprTagged<S, T>
 }
@@ -0,0 +1,139 @@
+use std::any::Any;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::{mem, ptr};
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Expression convertible to `Term`.
+///
+/// Implemented by all standard expressions.
+pub trait ExprTerm<S: Opset, C: ?Sized> {
+    /// Converts into term, keeping leaves other than values as they are.
+    fn to_term(&self) -> Term<S, C>;
+
+    /// Whether this leaf equals `other`, the node of another leaf.
+    ///
+    /// Used by `Term::same`; leaves are never the same by default.
+    fn same_leaf(&self, _other: &dyn Any) -> bool {
+        false
+    }
+}
+
+
+/// Expression tree open to rewriting.
+///
+/// Values are kept as constants, other leaves (tags, variables, ...)
+/// are kept opaque as dynamic expressions:
+///
+/// ```
+/// # use fuzzy_systems::{Expr, ExprTerm, Hamacher1, Term};
+/// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+/// let b = Expr::new(0.6);
+/// let d = !(a & b);
+///
+/// let term: Term<Hamacher1, ()> = d.to_term();
+/// match term {
+///     Term::Not(val) => match *val {
+///         Term::And(lhs, rhs) => {
+///             assert_eq!(lhs.to_expr().to_string(), "a");
+///             assert!(matches!(*rhs, Term::Const(_)));
+///         },
+///         _ => unreachable!()
+///     },
+///     _ => unreachable!()
+/// }
+/// ```
+pub enum Term<S: Opset, C: ?Sized> {
+    /// Constant value.
+    Const(Membership<S>),
+    /// Opaque leaf.
+    Leaf(ExprDyn<S, C>),
+    /// Negation.
+    Not(Box<Term<S, C>>),
+    /// Conjunction.
+    And(Box<Term<S, C>>, Box<Term<S, C>>),
+    /// Alternative.
+    Or(Box<Term<S, C>>, Box<Term<S, C>>)
+}
+
+impl<S: Opset, C: ?Sized> Term<S, C> {
+    /// Creates leaf from any expression.
+    pub fn leaf<E>(expr: E) -> Self
+    where
+        E: DynExpr<S, C> + 'static
+    {
+        Term::Leaf(ExprDyn::new(expr))
+    }
+
+    /// Number of nodes.
+    pub fn size(&self) -> usize {
+        match self {
+            Term::Const(_) | Term::Leaf(_) => 1,
+            Term::Not(val) => 1 + val.size(),
+            Term::And(lhs, rhs) | Term::Or(lhs, rhs) => {
+                1 + lhs.size() + rhs.size()
+            }
+        }
+    }
+
+    /// Whether the terms have the same structure and leaves.
+    ///
+    /// Opaque leaves are the same if they share the node or if
+    /// `ExprTerm::same_leaf` says so.
+    pub fn same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Term::Const(a), Term::Const(b)) => a == b,
+            (Term::Leaf(a), Term::Leaf(b)) => {
+                // zero-sized nodes share their address
+                let (a, b) = (a.node(), b.node());
+                let shared = mem::size_of_val(a) > 0 && ptr::addr_eq(a, b);
+                shared || a.same_leaf(b.as_any())
+            },
+            (Term::Not(a), Term::Not(b)) => a.same(b),
+            (Term::And(a, b), Term::And(c, d))
+            | (Term::Or(a, b), Term::Or(c, d)) => a.same(c) && b.same(d),
+            _ => false
+        }
+    }
+}
+
+impl<S: Opset + 'static, C: ?Sized + 'static> Term<S, C> {
+    /// Converts back into expression.
+    pub fn to_expr(&self) -> ExprDyn<S, C> {
+        match self {
+            Term::Const(val) => ExprDyn::new(ExprValue::from_membership(*val)),
+            Term::Leaf(expr) => expr.clone(),
+            Term::Not(val) => !val.to_expr(),
+            Term::And(lhs, rhs) => lhs.to_expr() & rhs.to_expr(),
+            Term::Or(lhs, rhs) => lhs.to_expr() | rhs.to_expr()
+        }
+    }
+}
+
+impl<S: Opset, C: ?Sized> Clone for Term<S, C> {
+    fn clone(&self) -> Self {
+        match self {
+            Term::Const(val) => Term::Const(*val),
+            Term::Leaf(expr) => Term::Leaf(expr.clone()),
+            Term::Not(val) => Term::Not(val.clone()),
+            Term::And(lhs, rhs) => Term::And(lhs.clone(), rhs.clone()),
+            Term::Or(lhs, rhs) => Term::Or(lhs.clone(), rhs.clone())
+        }
+    }
+}
+
+impl<S: Opset, C: ?Sized> Debug for Term<S, C> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Term::Const(val) => write!(f, "Const({:?})", val),
+            Term::Leaf(expr) => write!(f, "Leaf({:?})", expr),
+            Term::Not(val) => write!(f, "Not({:?})", val),
+            Term::And(lhs, rhs) => write!(f, "And({:?}, {:?})", lhs, rhs),
+            Term::Or(lhs, rhs) => write!(f, "Or({:?}, {:?})", lhs, rhs)
+        }
+    }
+}
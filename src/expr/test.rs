@@ -1,9 +1,19 @@
+// `Expr::new` is the documented shorthand and names the bare trait.
+#![allow(bare_trait_objects)]
+// Tests spell out copies of expressions as clones.
+#![allow(clippy::clone_on_copy)]
+
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::fmt::Display;
 
 use super::*;
 use super::tags::*;
 
-use crate::Hamacher1;
+use crate::{
+    Hamacher0, Hamacher1, Hamacher2, Membership, Opset, OpsetLaws, Yager1,
+    YagerInf
+};
 
 
 #[test]
@@ -143,6 +153,79 @@ fn order_big() {
     assert_eq!(e.to_string(), "(a | ((b & c) & !d))");
 }
 
+#[test]
+fn order_many() {
+    struct Rule<E> {
+        a: E,
+        b: E
+    }
+    let r = Rule {
+        a: Expr::<YagerInf>::new(0.1),
+        b: Expr::new(0.9)
+    };
+
+    let e = fuzzy_math!(
+        r.a & r.b | r.a & r.b | r.a & r.b | r.a & r.b | r.a & r.b |
+        r.a & r.b | r.a & r.b | r.a & r.b | r.a & r.b | r.a & r.b |
+        r.a & r.b | r.a & r.b | r.a & r.b | r.a & r.b | r.a & r.b |
+        r.a & r.b | r.a & r.b | r.a & r.b | r.a & r.b | !r.a & r.b |
+        r.a & r.b & r.a & r.b & r.a & r.b & r.a & r.b & r.a & r.b
+    );
+    assert_eq!(e.to_value().as_raw(), 0.9);
+    assert_eq!(e.to_string().matches('|').count(), 20);
+    assert_eq!(e.to_string().matches('&').count(), 29);
+}
+
+#[test]
+fn literals() {
+    let a = Expr::<Hamacher1>::new(0.4).with_tag(TagA);
+
+    let d = fuzzy_math!(a & 1 | 0);
+    assert_eq!(d.to_string(), "((a & 1) | 0)");
+    assert!((d.to_value().as_raw() - 0.4).abs() < 1e-12);
+
+    let d = fuzzy_math!(!(0.5 | a));
+    assert_eq!(d.to_string(), "!(0.5 | a)");
+}
+
+#[test]
+fn downstream_expr() {
+    // implements `Expr` only, as outside of the crate
+    #[derive(Clone, Copy)]
+    struct Half;
+
+    impl Expr<Hamacher1> for Half {
+        fn to_value(&self) -> Membership<Hamacher1> {
+            Membership::new(0.5)
+        }
+    }
+
+    let a = Expr::<Hamacher1>::new(0.0);
+    let d = a.or(Half);
+    assert_eq!(d.to_value().as_raw(), 0.5);
+
+    let d = fuzzy_math!(!Half & Half | a);
+    assert_eq!(d.to_value().as_raw(), 0.25);
+
+    // wrapped dynamically as opaque leaves
+    let half = ExprDyn::<Hamacher1>::opaque_value("half", Half);
+    let d = !half.clone() & half | ExprDyn::new(a);
+    assert_eq!(d.to_string(), "((!half & half) | 0)");
+    assert_eq!(d.to_value().as_raw(), 0.25);
+    assert_eq!(format!("{:?}", ExprDyn::<Hamacher1>::opaque_value("half", Half)),
+        "ExprDyn(ExprOpaque(\"half\"))");
+    assert_eq!(Explanation::eval(&d).value(), d.to_value());
+
+    let half = ExprDyn::<Hamacher1, [Membership<Hamacher1>]>::opaque_value(
+        "half", Half
+    );
+    let d = half & Expr::var(0);
+    let input = [Membership::new(0.8)];
+    assert_eq!(d.to_string(), "(half & 0)");
+    assert!((d.eval_with(&input[..]).as_raw() - 0.4).abs() < 1e-12);
+    assert_eq!(d.compile().eval_with(&input[..]), d.eval_with(&input[..]));
+}
+
 #[test]
 fn zero_cost_tag() {
     let a = Expr::<Hamacher1>::new(0.1);
@@ -155,3 +238,1092 @@ fn zero_cost_tag() {
     assert_eq!(size_of(a), size_of(a_tagged));
     assert_ne!(size_of(a), size_of(a_named));
 }
+
+#[test]
+fn vars() {
+    let a = Expr::<Hamacher1>::var(0);
+    let b = Expr::var(1);
+    let c = Expr::var(2);
+    let d = (a | b) & !c;
+
+    assert_eq!(d.to_string(), "((0 | 1) & !2)");
+
+    let input = [0.1, 0.6, 0.4].map(Hamacher1::member);
+    assert!((d.eval_with(&input).as_raw() - 0.384).abs() < 0.0001);
+    assert!((d.eval_with(&input[..]).as_raw() - 0.384).abs() < 0.0001);
+    assert!((d.eval_with(&input.to_vec()).as_raw() - 0.384).abs() < 0.0001);
+
+    let input = [0.0, 1.0, 0.0].map(Hamacher1::member);
+    assert_eq!(d.eval_with(&input).as_raw(), 1.0);
+}
+
+#[test]
+fn vars_mixed() {
+    let a = Expr::<YagerInf>::var(Index::<1>);
+    let b = Expr::new(0.5).with_tag(TagB);
+    let c = Expr::var(Index::<0>);
+    let d = a & (b | c);
+
+    assert_eq!(d.to_string(), "(x1 & (b | x0))");
+
+    let input = (YagerInf::member(0.7), Membership::new(0.9));
+    assert_eq!(d.eval_with(&input).as_raw(), 0.7);
+
+    let input = (YagerInf::member(0.2), Membership::new(0.4));
+    assert_eq!(d.eval_with(&input).as_raw(), 0.4);
+}
+
+#[test]
+fn closed_with_any_context() {
+    let a = Expr::<Hamacher1>::new(0.1);
+    let b = Expr::new(0.6).with_tag("b");
+    let d = (a | b).as_left::<ExprValue<Hamacher1>>();
+
+    assert_eq!(d.eval_with(&()), d.to_value());
+    assert_eq!(d.eval_with(&[0.3].map(Hamacher1::member)), d.to_value());
+}
+
+#[test]
+fn dynamic() {
+    let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+    let b = Expr::new(0.6).with_tag(TagB);
+    let c = Expr::new(0.4).with_tag("c");
+    let expected = (a | b) & !c;
+
+    let a = ExprDyn::<Hamacher1>::new(a);
+    let b = ExprDyn::new(b);
+    let c = ExprDyn::new(c);
+    let d = (a.clone() | b) & !c;
+
+    assert_eq!(d.to_string(), expected.to_string());
+    assert_eq!(d.to_value(), expected.to_value());
+    assert_eq!(d.clone().to_value(), expected.to_value());
+    assert_eq!(format!("{:?}", a), "ExprDyn(ExprTagged(Membership(0.1), \"a\"))");
+}
+
+#[test]
+fn dynamic_shared() {
+    let a = ExprRc::<Yager1>::new(Expr::new(0.3).with_tag(TagA));
+    let b = ExprArc::<Yager1>::new(Expr::new(0.5).with_tag(TagB));
+    let c = a.clone() | !a;
+    let d = b.clone() & b;
+
+    assert_eq!(c.to_string(), "(a | !a)");
+    assert_eq!(c.to_value().as_raw(), 1.0);
+    assert_eq!(d.to_string(), "(b & b)");
+    assert_eq!(d.to_value().as_raw(), 0.0);
+    assert!(format!("{:?}", c).starts_with("ExprRc("));
+    assert!(format!("{:?}", d).starts_with("ExprArc("));
+
+    fn send_sync<T: Send + Sync>(_val: &T) {}
+    send_sync(&d);
+}
+
+#[test]
+fn dynamic_vars() {
+    type Input = [Membership<YagerInf>; 2];
+    let a = ExprDyn::<YagerInf, Input>::new(Expr::var(0));
+    let b = ExprDyn::new(Expr::var(1));
+    let c = !a & (b | Expr::new(0.5));
+
+    assert_eq!(c.to_string(), "(!0 & (1 | 0.5))");
+
+    let input = [0.2, 0.9].map(YagerInf::member);
+    assert_eq!(c.eval_with(&input).as_raw(), 0.8);
+
+    let input = [0.7, 0.1].map(YagerInf::member);
+    assert!((c.eval_with(&input).as_raw() - 0.3).abs() < 0.0001);
+}
+
+#[test]
+fn parse_round_trip() {
+    let mut memberships = HashMap::new();
+    memberships.insert("a", Hamacher1::member(0.1));
+    memberships.insert("b", Hamacher1::member(0.6));
+    memberships.insert("c", Hamacher1::member(0.4));
+    memberships.insert("d", Hamacher1::member(0.3));
+
+    let a = Expr::<Hamacher1>::new(0.1).with_tag(TagA);
+    let b = Expr::new(0.6).with_tag(TagB);
+    let c = Expr::new(0.4).with_tag(TagC);
+    let d = Expr::new(0.3).with_tag(TagD);
+    let e = (a | !(b & !!c)) & (!d | Expr::new(1.0)) | Expr::new(0.25);
+
+    let parsed = ExprDyn::parse(&e.to_string(), &memberships).unwrap();
+    assert_eq!(parsed.to_string(), e.to_string());
+    assert_eq!(parsed.to_value(), e.to_value());
+}
+
+#[test]
+fn parse_precedence() {
+    let resolve = |ident: &str| {
+        let raw = match ident {
+            "a" => 0.1,
+            "b" => 0.2,
+            "c" => 0.3,
+            "d" => 0.4,
+            _ => return None
+        };
+        Some(ExprDyn::<Hamacher1>::new(Expr::new(raw).with_tag(ident.to_string())))
+    };
+    let parse = |src| ExprDyn::parse(src, &resolve).unwrap().to_string();
+
+    assert_eq!(parse("a & b | c"), "((a & b) | c)");
+    assert_eq!(parse("a | b & c"), "(a | (b & c))");
+    assert_eq!(parse("a | b | c | d"), "(((a | b) | c) | d)");
+    assert_eq!(parse("!a & b | !(c | d)"), "((!a & b) | !(c | d))");
+    assert_eq!(parse("  (( a ))&!!b "), "(a & !!b)");
+}
+
+#[test]
+fn parse_errors() {
+    let memberships: HashMap<String, Membership<Yager1>> = HashMap::new();
+    let error = |src| {
+        let err = ExprDyn::<Yager1>::parse(src, &memberships).unwrap_err();
+        (err.kind().clone(), err.pos())
+    };
+
+    assert_eq!(error("0.5 & x"), (ParseErrorKind::UnknownIdent("x".into()), 6));
+    assert_eq!(error("0.5 & 1.5"), (ParseErrorKind::InvalidNumber("1.5".into()), 6));
+    assert_eq!(error("0.5 + 0.2"), (ParseErrorKind::InvalidChar('+'), 4));
+    assert_eq!(error("(0.5 & 0.2"), (ParseErrorKind::Expected("`)`"), 10));
+    assert_eq!(error("0.5 0.2"), (ParseErrorKind::Expected("operator"), 4));
+    assert_eq!(error("0.5 & | 0.2"), (ParseErrorKind::Expected("operand"), 6));
+    assert_eq!(error(""), (ParseErrorKind::Expected("operand"), 0));
+}
+
+#[test]
+fn parse_too_deep() {
+    let memberships: HashMap<String, Membership<Yager1>> = HashMap::new();
+    let parse = |src: &str| ExprDyn::<Yager1>::parse(src, &memberships);
+    let nested = |open: &str, n, close: &str| {
+        format!("{}0.5{}", open.repeat(n), close.repeat(n))
+    };
+
+    assert!(parse(&nested("(", 256, ")")).is_ok());
+    assert!(parse(&nested("!", 256, "")).is_ok());
+
+    let err = parse(&nested("(", 257, ")")).unwrap_err();
+    assert_eq!((err.kind().clone(), err.pos()), (ParseErrorKind::TooDeep, 256));
+    let err = parse(&nested("!", 200_000, "")).unwrap_err();
+    assert_eq!(err.kind(), &ParseErrorKind::TooDeep);
+    let err = parse(&nested("(!", 200_000, ")")).unwrap_err();
+    assert_eq!(err.kind(), &ParseErrorKind::TooDeep);
+
+    // flat chains build left-deep trees
+    let chain = |op: &str, n| vec!["0.5"; n].join(op);
+    let err = parse(&chain("|", 300_000)).unwrap_err();
+    // at the 1025th operator
+    assert_eq!((err.kind().clone(), err.pos()), (ParseErrorKind::TooDeep, 4 * 1024 + 3));
+    let err = parse(&chain(" & ", 300_000)).unwrap_err();
+    assert_eq!(err.kind(), &ParseErrorKind::TooDeep);
+    let err = parse(&format!("({}) | {}", chain("&", 1000), chain("|", 100))).unwrap_err();
+    assert_eq!(err.kind(), &ParseErrorKind::TooDeep);
+
+    // deepest trees are traversed and dropped
+    let src = format!("{}|{}", nested("!", 256, ""), chain("|", 1024));
+    let deepest = parse(&src).unwrap();
+    assert_eq!(deepest.to_value().as_raw(), 1.0);
+    assert_eq!(deepest.compile().to_value(), deepest.to_value());
+    assert_eq!(deepest.simplify().to_value(), deepest.to_value());
+    assert_eq!(deepest.nnf().to_expr().to_value(), deepest.to_value());
+    assert_eq!(Explanation::eval(&deepest).value(), deepest.to_value());
+    assert!(deepest.to_string().starts_with("(((("));
+    assert!(!DotExporter::new().export(&deepest).is_empty());
+}
+
+#[test]
+fn order_long() {
+    // erasure:
+    type H = Hamacher1;
+    trait ExprStr = Expr<H> + Display + Copy;
+    fn abcde() -> (impl ExprStr, impl ExprStr, impl ExprStr, impl ExprStr, impl ExprStr) {
+        let a = Expr::new(0.1).with_tag(TagA);
+        let b = Expr::new(0.2).with_tag(TagB);
+        let c = Expr::new(0.3).with_tag(TagC);
+        let d = Expr::new(0.4).with_tag(TagD);
+        let e = Expr::new(0.5).with_tag(TagE);
+        (a, b, c, d, e)
+    }
+    let (a, b, c, d, e) = abcde();
+
+    // test:
+    let f = fuzzy_math!(a & b | c & !d | e);
+    assert_eq!(f.to_string(), "(((a & b) | (c & !d)) | e)");
+
+    let f = fuzzy_math!(!a & !b & !c | !d & e);
+    assert_eq!(f.to_string(), "(((!a & !b) & !c) | (!d & e))");
+
+    let f = fuzzy_math!(!!a | !(b | c) & (d | e) & a);
+    assert_eq!(f.to_string(), "(!!a | ((!(b | c) & (d | e)) & a))");
+
+    let f = fuzzy_math!(a & (b | (c & (d | !e))));
+    assert_eq!(f.to_string(), "(a & (b | (c & (d | !e))))");
+}
+
+#[test]
+fn order_generic_operands() {
+    struct Rule<A, B> {
+        a: A,
+        b: B
+    }
+
+    impl<A: Clone, B: Clone> Rule<A, B> {
+        fn b(&self) -> B {
+            self.b.clone()
+        }
+    }
+
+    fn eval<S, A, B, C>(rule: &Rule<A, B>, c: C) -> impl Expr<S> + Display
+    where
+        S: Opset,
+        A: Expr<S> + Clone + Display,
+        B: Expr<S> + Clone + Display,
+        C: Expr<S> + Display
+    {
+        fuzzy_math!(rule.a.clone() & 0.5 | !rule.b() & c | 1.0)
+    }
+
+    let rule = Rule {
+        a: Expr::<Hamacher1>::new(0.1).with_tag(TagA),
+        b: Expr::new(0.6).with_tag(TagB)
+    };
+    let d = eval(&rule, Expr::new(0.3).with_tag(TagC));
+    assert_eq!(d.to_string(), "(((a & 0.5) | (!b & c)) | 1)");
+    assert_eq!(d.to_value().as_raw(), 1.0);
+}
+
+#[test]
+fn ops_generic_rhs() {
+    fn combine<S, A>(a: A) -> impl Expr<S> + Display
+    where
+        S: Opset,
+        A: Expr<S> + Copy + Display
+    {
+        let b = Expr::<S>::new(0.5).with_tag(TagB);
+        fuzzy_math!(!b & a | b & a.not())
+    }
+
+    let d = combine(Expr::<YagerInf>::new(0.3).with_tag(TagA));
+    assert_eq!(d.to_string(), "((!b & a) | (b & !a))");
+    assert_eq!(d.to_value().as_raw(), 0.5);
+}
+
+#[test]
+fn order_vars() {
+    let a = Expr::<YagerInf>::var(0);
+    let b = Expr::var(1);
+    let c = Expr::var(2);
+    let d = fuzzy_math!(0.5 & a | !b & c);
+
+    assert_eq!(d.to_string(), "((0.5 & 0) | (!1 & 2))");
+
+    let input = [0.2, 0.3, 0.9].map(YagerInf::member);
+    assert_eq!(d.eval_with(&input).as_raw(), 0.7);
+}
+
+#[test]
+fn format_default_is_display() {
+    let a = Expr::<Hamacher1>::new(0.1).with_tag(TagA);
+    let b = Expr::new(0.6);
+    let c = Expr::var(Index::<2>);
+    let d = (a | !(b & !!c)) & (a | b) | c;
+
+    let fmt = ExprFormatter::default();
+    assert_eq!(fmt.display(&d).to_string(), d.to_string());
+
+    let d = ExprDyn::<Hamacher1, (Membership<Hamacher1>,)>::new(a & b);
+    assert_eq!(fmt.display(&d).to_string(), d.to_string());
+}
+
+#[test]
+fn format_minimal_parens() {
+    let a = Expr::<YagerInf>::new(0.1).with_tag(TagA);
+    let b = Expr::new(0.2).with_tag(TagB);
+    let c = Expr::new(0.3).with_tag(TagC);
+    let fmt = ExprFormatter::new().minimal_parens(true);
+    let show = |e: &dyn ExprFormat| fmt.display(e).to_string();
+
+    assert_eq!(show(&(a | b | c)), "a | b | c");
+    assert_eq!(show(&(a | (b | c))), "a | (b | c)");
+    assert_eq!(show(&(a & b | c)), "a & b | c");
+    assert_eq!(show(&(a & (b | c))), "a & (b | c)");
+    assert_eq!(show(&((a | b) & c)), "(a | b) & c");
+    assert_eq!(show(&(a | b & c)), "a | b & c");
+    assert_eq!(show(&!!(a & b)), "!!(a & b)");
+    assert_eq!(show(&(!a & !b)), "!a & !b");
+}
+
+#[test]
+fn format_notations() {
+    let a = Expr::<YagerInf>::new(0.1).with_tag("a");
+    let b = Expr::new(0.2);
+    let c = Expr::var("c");
+    let d = !!a & (b | !c);
+
+    let fmt = ExprFormatter::new().notation(Notation::Words);
+    assert_eq!(fmt.display(&d).to_string(), "(not not a and (0.2 or not c))");
+
+    let fmt = fmt.notation(Notation::Logic).with_values(true);
+    assert_eq!(fmt.display(&d).to_string(), "(¬¬a=0.1 ∧ (0.2 ∨ ¬c))");
+}
+
+#[test]
+fn format_round_trip() {
+    let mut memberships = HashMap::new();
+    memberships.insert("a", Hamacher1::member(0.1));
+    memberships.insert("b", Hamacher1::member(0.6));
+    memberships.insert("c", Hamacher1::member(0.4));
+
+    let a = Expr::<Hamacher1>::new(0.1).with_tag(TagA);
+    let b = Expr::new(0.6).with_tag(TagB);
+    let c = Expr::new(0.4).with_tag(TagC);
+    let d = (a | !(b | c) & a) | (b & (c | a)) & !!c;
+
+    let fmt = ExprFormatter::new().minimal_parens(true);
+    let minimal = fmt.display(&d).to_string();
+    let parsed = ExprDyn::parse(&minimal, &memberships).unwrap();
+    assert_eq!(parsed.to_string(), d.to_string());
+    assert_eq!(parsed.to_value(), d.to_value());
+}
+
+#[test]
+fn export_dot_vars() {
+    let a = Expr::<YagerInf>::var(Index::<0>);
+    let b = Expr::new(0.5).with_tag("say \"b\"");
+    let d = ExprEither::<YagerInf, _, ExprValue<YagerInf>>::left(a | b);
+    let input = (YagerInf::member(0.25),);
+
+    let dot = DotExporter::new()
+        .notation(Notation::Ascii)
+        .with_values(true)
+        .export_with(&d, &input);
+    assert_eq!(dot, concat!(
+        "digraph expr {\n",
+        "    n0 [label=\"x0\\n0.25\"];\n",
+        "    n1 [label=\"say \\\"b\\\"\\n0.5\"];\n",
+        "    n2 [label=\"|\\n0.5\"];\n",
+        "    n2 -> n0;\n",
+        "    n2 -> n1;\n",
+        "}\n"
+    ));
+
+    let d = ExprDyn::<YagerInf>::new(!Expr::new(0.3));
+    let dot = DotExporter::new().export(&d);
+    assert_eq!(dot, concat!(
+        "digraph expr {\n",
+        "    n0 [label=\"0.3\"];\n",
+        "    n1 [label=\"¬\"];\n",
+        "    n1 -> n0;\n",
+        "}\n"
+    ));
+}
+
+#[test]
+fn export_latex() {
+    let a = Expr::<Hamacher1>::new(0.1).with_tag("x_1");
+    let b = Expr::new(0.6).with_tag("x_2");
+    let c = Expr::var("y");
+    let d = (a | !b) & c;
+
+    let fmt = ExprFormatter::new()
+        .notation(Notation::Latex)
+        .minimal_parens(true)
+        .with_values(true);
+    assert_eq!(
+        fmt.display(&d).to_string(),
+        r"(\mathrm{x\_1}=0.1 \lor \lnot \mathrm{x\_2}=0.6) \land \mathrm{y}"
+    );
+}
+
+#[test]
+fn export_latex_escapes() {
+    let a = Expr::<Hamacher1>::new(0.1).with_tag(r"a\b^c~d{e}");
+    let fmt = ExprFormatter::new().notation(Notation::Latex);
+    assert_eq!(
+        fmt.display(&!a).to_string(),
+        r"\lnot \mathrm{a\textbackslash{}b\^{}c\~{}d\{e\}}"
+    );
+}
+
+/// Collects tags and variables, in order.
+struct Names(Vec<String>);
+
+impl<S: Opset> Fold<S> for Names {
+    type Output = ();
+
+    fn value(&mut self, _val: Membership<S>) {}
+
+    fn tagged(&mut self, tag: &dyn Display, _val: Membership<S>) {
+        self.0.push(tag.to_string());
+    }
+
+    fn var(&mut self, idx: &dyn Display, _val: Option<Membership<S>>) {
+        self.0.push(idx.to_string());
+    }
+
+    fn not(&mut self, _val: ()) {}
+    fn and(&mut self, _lhs: (), _rhs: ()) {}
+    fn or(&mut self, _lhs: (), _rhs: ()) {}
+}
+
+/// Evaluates with opset `T` instead of the expression's own.
+struct EvalAs<T: Opset>(Membership<T>);
+
+impl<S: Opset, T: Opset> Fold<S> for EvalAs<T> {
+    type Output = Membership<T>;
+
+    fn value(&mut self, val: Membership<S>) -> Membership<T> {
+        Membership::new(val.as_raw())
+    }
+
+    fn var(&mut self, _idx: &dyn Display, val: Option<Membership<S>>)
+        -> Membership<T> {
+
+        val.map_or(self.0, |val| Membership::new(val.as_raw()))
+    }
+
+    fn not(&mut self, val: Membership<T>) -> Membership<T> {
+        T::not(val)
+    }
+
+    fn and(&mut self, lhs: Membership<T>, rhs: Membership<T>)
+        -> Membership<T> {
+
+        T::and(lhs, rhs)
+    }
+
+    fn or(&mut self, lhs: Membership<T>, rhs: Membership<T>)
+        -> Membership<T> {
+
+        T::or(lhs, rhs)
+    }
+
+    fn apply(&mut self, op: &dyn Modifier<S>, val: Membership<T>)
+        -> Membership<T> {
+
+        Membership::new(op.apply(Membership::new(val.as_raw())).as_raw())
+    }
+}
+
+#[test]
+fn fold_names() {
+    let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+    let b = Expr::new(0.6);
+    let c = Expr::var("c");
+    let d = (a | !b.hedge(Hedge::Very))
+        & ExprEither::<_, _, ExprValue<_>>::left(c);
+
+    let mut names = Names(Vec::new());
+    Fold::<Hamacher1>::fold(&mut names, &d);
+    assert_eq!(names.0, ["a", "c"]);
+
+    let d = ExprDyn::<Hamacher1>::parse("x & !(y | 0.5)", &|name: &str| {
+        Some(ExprDyn::new(Expr::new(0.2).with_tag(name.to_owned())))
+    }).unwrap();
+    let mut names = Names(Vec::new());
+    names.fold(&d);
+    assert_eq!(names.0, ["x", "y"]);
+}
+
+#[test]
+fn fold_other_opset() {
+    let a = Expr::<Hamacher1>::new(0.1);
+    let b = Expr::new(0.6);
+    let c = Expr::new(0.4).with_tag("c");
+    let d = (a | b) & !c;
+
+    let expected = (
+        Membership::<YagerInf>::new(0.1) | Membership::new(0.6)
+    ) & !Membership::new(0.4);
+    assert_eq!(EvalAs(Membership::new(0.0)).fold(&d), expected);
+
+    let x = Expr::<Yager1>::var(0);
+    let y = Expr::var(1);
+    let d = x & !y;
+    let input = [Membership::new(0.7), Membership::new(0.2)];
+    let expected = Membership::<YagerInf>::new(0.7) & Membership::new(0.8);
+    assert_eq!(EvalAs(Membership::new(0.0)).fold_with(&d, &input), expected);
+    assert_eq!(
+        EvalAs(Membership::<YagerInf>::new(0.5)).fold(&d),
+        Membership::new(0.5)
+    );
+}
+
+/// Random tree over a small pool of leaves, so repetitions are common.
+fn random_tree<S>(seed: &mut u64, depth: u32) -> ExprDyn<S, Vec<Membership<S>>>
+where
+    S: OpsetLaws + 'static
+{
+    // xorshift
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 7;
+    *seed ^= *seed << 17;
+    let pick = *seed % 8;
+
+    if depth == 0 || pick < 2 {
+        return match *seed / 8 % 6 {
+            0 => ExprDyn::new(Expr::new(0.0)),
+            1 => ExprDyn::new(Expr::new(1.0)),
+            2 => ExprDyn::new(Expr::new(0.3).with_tag("a")),
+            3 => ExprDyn::new(Expr::new(0.8).with_tag("b")),
+            n => ExprDyn::new(Expr::var(n as usize - 4))
+        };
+    }
+    match pick {
+        2 | 3 => !random_tree(seed, depth - 1),
+        4 | 5 => random_tree(seed, depth - 1) & random_tree(seed, depth - 1),
+        _ => random_tree(seed, depth - 1) | random_tree(seed, depth - 1)
+    }
+}
+
+#[test]
+fn simplify_preserves_values() {
+    fn check<S: OpsetLaws + 'static>() {
+        let inputs = [
+            vec![Membership::new(0.15), Membership::new(0.6)],
+            vec![Membership::new(0.9), Membership::new(0.45)]
+        ];
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        let mut shrunk = 0;
+
+        for _ in 0..500 {
+            let d = random_tree::<S>(&mut seed, 5);
+            let s = d.simplify();
+            assert!(s.to_term().size() <= d.to_term().size());
+            if s.to_term().size() < d.to_term().size() {
+                shrunk += 1;
+            }
+
+            for input in &inputs {
+                let expected = d.eval_with(input).as_raw();
+                // e.g. Hamacher0's `0 & 0`
+                if expected.is_nan() {
+                    continue;
+                }
+                let actual = s.eval_with(input).as_raw();
+                assert!(
+                    (expected - actual).abs() < 1e-9,
+                    "{} = {}, simplified {} = {}", d, expected, s, actual
+                );
+            }
+        }
+        assert!(shrunk > 0);
+    }
+
+    check::<Yager1>();
+    check::<YagerInf>();
+    check::<Hamacher0>();
+    check::<Hamacher1>();
+    check::<Hamacher2>();
+}
+
+#[test]
+fn simplify_rules() {
+    let a = Expr::<YagerInf>::new(0.3).with_tag("a");
+    let b = Expr::new(0.8).with_tag("b");
+    let x = Expr::var("x");
+
+    let d = ExprDyn::<YagerInf, HashMap<&str, Membership<_>>>::new(!!x & x);
+    assert_eq!(d.simplify().to_string(), "x");
+
+    let d = ExprDyn::<YagerInf>::new((a | Expr::new(0.0)) & !!b);
+    assert_eq!(d.simplify().to_string(), "(a & b)");
+
+    let d = ExprDyn::<YagerInf>::new(!(!a & b));
+    assert_eq!(d.simplify().to_string(), "(a | !b)");
+
+    let d = ExprDyn::<YagerInf>::new(!(a & b));
+    assert_eq!(d.simplify().to_string(), "!(a & b)");
+
+    let d = ExprDyn::<YagerInf>::new(!a | !b);
+    assert_eq!(d.simplify().to_string(), "!(a & b)");
+
+    let d = ExprDyn::<YagerInf>::new(!Expr::new(0.25) & Expr::new(0.5));
+    assert_eq!(d.simplify().to_string(), "0.5");
+
+    // not a t-norm, keeps its constants and negated operands
+    let a = Expr::<Hamacher2>::new(0.3).with_tag("a");
+    let d = ExprDyn::<Hamacher2>::new(!!a & Expr::new(1.0) | !a & !a);
+    assert_eq!(d.simplify().to_string(), "((a & 1) | (!a & !a))");
+}
+
+#[test]
+fn term_same_leaves() {
+    type C = (Membership<YagerInf>, Membership<YagerInf>);
+    let leaf = |e: ExprDyn<YagerInf, C>| e.to_term();
+
+    let a = Expr::<YagerInf>::new(0.3).with_tag("a");
+    assert!(leaf(ExprDyn::new(a)).same(&leaf(ExprDyn::new(a))));
+    let very = a.hedge(Hedge::Very);
+    assert!(!leaf(ExprDyn::new(a)).same(&leaf(ExprDyn::new(very))));
+
+    // same name, other value
+    let b = Expr::<YagerInf>::new(0.4).with_tag("a");
+    assert!(!leaf(ExprDyn::new(a)).same(&leaf(ExprDyn::new(b))));
+
+    // same `Debug` output, other tag types
+    let c = Expr::<YagerInf>::new(0.3).with_tag(String::from("a"));
+    assert_eq!(format!("{:?}", a), format!("{:?}", c));
+    assert!(!leaf(ExprDyn::new(a)).same(&leaf(ExprDyn::new(c))));
+
+    let x = Expr::<YagerInf>::var(Index::<0>);
+    let y = Expr::<YagerInf>::var(Index::<1>);
+    assert!(leaf(ExprDyn::new(x)).same(&leaf(ExprDyn::new(x))));
+    assert!(!leaf(ExprDyn::new(x)).same(&leaf(ExprDyn::new(y))));
+
+    let w = Term::Leaf(ExprDyn::new(a.weighted_and(0.5)));
+    assert!(w.same(&w.clone()));
+    assert!(w.same(&leaf(ExprDyn::new(a.weighted_and(0.5)))));
+}
+
+#[test]
+fn normal_forms() {
+    /// Whether negations are applied to leaves only.
+    fn is_nnf<S: Opset, C: ?Sized>(term: &Term<S, C>) -> bool {
+        match term {
+            Term::Const(_) | Term::Leaf(_) => true,
+            Term::Not(val) => matches!(**val, Term::Leaf(_)),
+            Term::And(lhs, rhs) | Term::Or(lhs, rhs) => {
+                is_nnf(lhs) && is_nnf(rhs)
+            }
+        }
+    }
+
+    /// Whether no `inner` operator contains an `outer` one.
+    fn is_layered<S: Opset, C: ?Sized>(term: &Term<S, C>, dnf: bool)
+        -> bool {
+
+        fn has_outer<S: Opset, C: ?Sized>(term: &Term<S, C>, dnf: bool)
+            -> bool {
+
+            match (term, dnf) {
+                (Term::Or(..), true) | (Term::And(..), false) => true,
+                (Term::And(lhs, rhs), _) | (Term::Or(lhs, rhs), _) => {
+                    has_outer(lhs, dnf) || has_outer(rhs, dnf)
+                },
+                _ => false
+            }
+        }
+
+        match (term, dnf) {
+            (Term::Or(lhs, rhs), true) | (Term::And(lhs, rhs), false) => {
+                is_layered(lhs, dnf) && is_layered(rhs, dnf)
+            },
+            _ => !has_outer(term, dnf)
+        }
+    }
+
+    fn check<S: OpsetLaws + 'static>(exact_nnf: bool, exact_spread: bool) {
+        let input = vec![Membership::new(0.15), Membership::new(0.6)];
+        let mut seed = 0x1234_5678_9abc_def1;
+        let mut inexact = 0;
+
+        for _ in 0..200 {
+            let d = random_tree::<S>(&mut seed, 4);
+            let expected = d.eval_with(&input).as_raw();
+
+            let forms = [
+                (d.nnf(), None),
+                (d.dnf(), Some(true)),
+                (d.cnf(), Some(false))
+            ];
+            for (form, dnf) in forms.iter() {
+                assert!(is_nnf(form.term()));
+                if let Some(dnf) = *dnf {
+                    assert!(is_layered(form.term(), dnf));
+                    assert!(!exact_spread || form.is_exact());
+                }
+                else {
+                    assert_eq!(form.is_exact(), exact_nnf);
+                }
+
+                let actual = form.to_expr().eval_with(&input).as_raw();
+                if form.is_exact() {
+                    assert!((expected - actual).abs() < 1e-9);
+                }
+                else if (expected - actual).abs() > 1e-9 {
+                    inexact += 1;
+                }
+            }
+        }
+        assert_eq!(inexact > 0, !exact_spread);
+    }
+
+    check::<YagerInf>(true, true);
+    check::<Yager1>(true, false);
+    check::<Hamacher1>(true, false);
+}
+
+#[test]
+fn explain_trace() {
+    let a = Expr::<Hamacher1>::new(0.2).with_tag("a");
+    let b = Expr::new(0.5);
+    let d = ExprDyn::<Hamacher1>::new(!(a & b)) | Expr::new(0.1);
+
+    let trace = d.explain();
+    assert_eq!(trace.value(), d.to_value());
+    assert_eq!(trace.step(), &Step::Or);
+
+    let not = &trace.children()[0];
+    assert_eq!(not.step(), &Step::Not);
+    assert_eq!(not.value(), Membership::new(0.9));
+    let a = &not.children()[0].children()[0];
+    assert_eq!(a.step(), &Step::Tagged("a".into()));
+    assert_eq!(trace.children()[1].step(), &Step::Value);
+    assert_eq!(format!("{:.2}", trace), concat!(
+        "or = 0.91\n",
+        "  not = 0.90\n",
+        "    and = 0.10\n",
+        "      a = 0.20\n",
+        "      0.50\n",
+        "  0.10\n"
+    ));
+
+    let x = Expr::var(1);
+    let d = !x;
+    let trace = Explanation::eval_with(&d, &vec![Membership::new(0.0); 2]);
+    assert_eq!(trace.children()[0].step(), &Step::Var("1".into()));
+    assert_eq!(trace.value(), Membership::<Yager1>::new(1.0));
+}
+
+#[test]
+fn nary() {
+    let a = Expr::<Hamacher1>::new(0.8).with_tag("a");
+    let b = Expr::new(0.5).with_tag("b");
+    let c = Expr::new(0.4);
+
+    let d = Expr::all([a, b, a]);
+    assert_eq!(d.to_string(), "(a & b & a)");
+    assert_eq!(d.to_value(), (a & b & a).to_value());
+
+    let d = Expr::any((a, !b & c, Expr::all(vec![c, c])));
+    assert_eq!(d.to_string(), "(a | (!b & 0.4) | (0.4 & 0.4))");
+    assert_eq!(d.to_value(), (a | !b & c | c & c).to_value());
+    assert_eq!(d.explain().value(), d.to_value());
+
+    let fmt = ExprFormatter::new().minimal_parens(true);
+    assert_eq!(fmt.display(&d).to_string(), "a | !b & 0.4 | 0.4 & 0.4");
+    let e = Expr::all((d, c));
+    assert_eq!(
+        fmt.display(&e).to_string(),
+        "(a | !b & 0.4 | 0.4 & 0.4) & 0.4"
+    );
+
+    let none: Vec<ExprValue<Hamacher1>> = vec![];
+    assert_eq!(Expr::all(none.clone()).to_string(), "1");
+    assert_eq!(Expr::any(none.clone()).to_value(), Membership::new(0.0));
+    assert_eq!(Expr::all([!a]).to_string(), "!a");
+    assert_eq!(fmt.display(&!Expr::any([a | b])).to_string(), "!(a | b)");
+
+    let d = ExprDyn::<Hamacher1>::new(Expr::all((a, b, c)));
+    let term = d.to_term();
+    assert_eq!(term.size(), 5);
+    assert_eq!(term.to_expr().to_string(), "((a & b) & 0.4)");
+    assert_eq!(term.to_expr().to_value(), d.to_value());
+}
+
+#[test]
+fn nary_vars() {
+    let x = Expr::<Yager1>::var(0);
+    let y = Expr::var(1);
+    let d = Expr::all((x, !y)) | Expr::any(vec![x, y, Expr::var(2)]);
+    let input = [0.2, 0.3, 0.4].map(Membership::new);
+
+    let expected = (x & !y) | (x | y | Expr::var(2));
+    assert_eq!(d.eval_with(&input), expected.eval_with(&input));
+    assert_eq!(d.to_string(), "((0 & !1) | (0 | 1 | 2))");
+
+    let mut names = Names(Vec::new());
+    Fold::<Yager1>::fold(&mut names, &d);
+    assert_eq!(names.0, ["0", "1", "0", "1", "2"]);
+    let trace = Explanation::eval_with(&d, &input);
+    assert_eq!(trace.value(), d.eval_with(&input));
+}
+
+#[test]
+fn weighted() {
+    let a = Expr::<Hamacher1>::new(0.2).with_tag("a");
+    let b = Expr::new(0.9).with_tag("b");
+    let w = [0.0, 0.25, 0.5, 1.0];
+
+    for &wa in &w {
+        for &wb in &w {
+            let d = a.weighted_and(wa) & b.weighted_and(wb);
+            let expected = Hamacher1::and_weighted([
+                (a.to_value(), Membership::new(wa)),
+                (b.to_value(), Membership::new(wb))
+            ]);
+            assert_eq!(d.to_value(), expected);
+
+            let d = a.weighted_or(wa) | b.weighted_or(wb);
+            let expected = Hamacher1::or_weighted([
+                (a.to_value(), Membership::new(wa)),
+                (b.to_value(), Membership::new(wb))
+            ]);
+            assert_eq!(d.to_value(), expected);
+        }
+    }
+
+    // full weight keeps the operand, no weight makes it neutral
+    assert_eq!((a.weighted_and(1.0) & b).to_value(), (a & b).to_value());
+    assert_eq!((a.weighted_and(0.0) & b).to_value(), b.to_value());
+    assert_eq!((a.weighted_or(1.0) | b).to_value(), (a | b).to_value());
+    assert_eq!((a.weighted_or(0.0) | b).to_value(), b.to_value());
+
+    let x = Expr::<Hamacher1>::var(0usize);
+    let d = !ExprWeighted::new(x, Weight::And(Membership::new(0.5))) & b;
+    let input = [Membership::new(0.8)];
+    assert!((d.eval_with(&input).as_raw() - 0.2 * 0.9).abs() < 1e-6);
+    assert_eq!(d.to_string(), "(!weighted_and(0, 0.5) & b)");
+
+    let trace = Explanation::eval_with(&d, &input);
+    assert_eq!(trace.value(), d.eval_with(&input));
+    assert_eq!(
+        trace.children()[0].children()[0].step(),
+        &Step::Apply("weighted_and(0.5)".to_string())
+    );
+
+    let e = ExprDyn::<Hamacher1, [Membership<Hamacher1>; 1]>::new(d);
+    assert_eq!(e.simplify().eval_with(&input), d.eval_with(&input));
+}
+
+#[test]
+fn hedges() {
+    let a = Expr::<Hamacher1>::new(0.6).with_tag("a");
+    let hedges = [
+        (Hedge::Very, 0.36),
+        (Hedge::Extremely, 0.216),
+        (Hedge::Somewhat, 0.6_f64.sqrt()),
+        (Hedge::Slightly, 0.6_f64.powf(1.7)),
+        (Hedge::Indeed, 0.68),
+        (Hedge::Pow(1.5), 0.6_f64.powf(1.5))
+    ];
+    for (hedge, expected) in hedges {
+        assert!((a.hedge(hedge).to_value().as_raw() - expected).abs() < 1e-9);
+    }
+
+    let x = Expr::<Hamacher1>::var("x");
+    let d = ExprHedge::new(ExprHedge::new(x, Hedge::Very), Hedge::Very) | a;
+    assert_eq!(d.to_string(), "(very(very(x)) | a)");
+
+    let fmt = ExprFormatter::new().notation(Notation::Latex);
+    assert_eq!(
+        fmt.display(&d).to_string(),
+        r"(\mathrm{very}(\mathrm{very}(\mathrm{x})) \lor \mathrm{a})"
+    );
+
+    let mut input = HashMap::new();
+    input.insert("x", Membership::new(0.5));
+    let expected = Membership::new(0.0625) | a.to_value();
+    assert_eq!(d.eval_with(&input), expected);
+
+    let trace = Explanation::eval_with(&d, &input);
+    assert_eq!(
+        format!("{:.4}", trace),
+        "or = 0.6250\n  very = 0.0625\n    very = 0.2500\n      x = 0.5000\n\
+         \x20 a = 0.6000\n"
+    );
+}
+
+#[test]
+fn one_of() {
+    let a = Expr::<Hamacher1>::new(0.4).with_tag("a");
+    let b = Expr::new(0.6).with_tag("b");
+    let x = Expr::var(0usize);
+
+    let shapes: Vec<_> = (0..4)
+        .map(|i| ExprOneOf4::select(i, || a | b, || a & x, || !b, || x))
+        .collect();
+    let texts: Vec<_> = shapes.iter().map(ToString::to_string).collect();
+    assert_eq!(texts, ["(a | b)", "(a & 0)", "!b", "0"]);
+
+    let input = [Membership::new(0.3)];
+    let expected = [
+        (a | b).to_value(),
+        (a & x).eval_with(&input),
+        (!b).to_value(),
+        input[0]
+    ];
+    for (i, shape) in shapes.iter().enumerate() {
+        assert_eq!(shape.index(), i);
+        assert_eq!(shape.eval_with(&input), expected[i]);
+        assert_eq!(shape.clone().eval_with(&input), expected[i]);
+        let trace = Explanation::eval_with(shape, &input);
+        assert_eq!(trace.value(), expected[i]);
+    }
+
+    let d = ExprOneOf8::<_, _, ExprValue<_>, ExprValue<_>, ExprValue<_>,
+        ExprValue<_>, ExprValue<_>, ExprValue<_>, ExprValue<_>>::b0(a);
+    assert_eq!(format!("{:?}", d), format!("ExprOneOf8::B0({:?})", a));
+    let e = ExprDyn::<Hamacher1>::new(d) & b;
+    assert_eq!(e.to_value(), (a & b).to_value());
+}
+
+#[test]
+#[should_panic(expected = "Branch index out of range!")]
+fn one_of_out_of_range() {
+    let a = Expr::<Hamacher1>::new(0.4);
+    let d = ExprOneOf3::select(3, || a, || !a, || a & a);
+    d.to_value();
+}
+
+/// Context counting lookups of its variables.
+struct Counting(Vec<Membership<Hamacher1>>, Cell<usize>);
+
+impl Context<Hamacher1, usize> for Counting {
+    fn get(&self, idx: &usize) -> Membership<Hamacher1> {
+        self.1.set(self.1.get() + 1);
+        self.0[*idx]
+    }
+}
+
+struct CountingCols(Vec<Vec<Membership<Hamacher1>>>, Cell<usize>);
+
+impl Columns<Hamacher1, usize> for CountingCols {
+    fn column(&self, idx: &usize) -> &[Membership<Hamacher1>] {
+        self.1.set(self.1.get() + 1);
+        &self.0[*idx]
+    }
+}
+
+#[test]
+fn shared() {
+    let x = Expr::<Hamacher1>::var(0usize);
+    let y = Expr::var(1usize);
+    let z = Expr::var(2usize);
+
+    let plain = ExprShared::new(x | y);
+    let cached = ExprShared::cached(x | y);
+    assert!(plain.clone().ptr_eq(&plain) && !plain.ptr_eq(&cached));
+    let d = (plain.clone() & z) | (plain & !z);
+    let e = (cached.clone() & z) | (cached & !z);
+    assert_eq!(d.to_string(), e.to_string());
+
+    for raw in [[0.1, 0.2, 0.3], [0.6, 0.5, 0.4]] {
+        let input = Counting(raw.map(Membership::new).to_vec(), Cell::new(0));
+        let expected = ((x | y) & z) | ((x | y) & !z);
+        let expected = expected.eval_with(&input);
+
+        input.1.set(0);
+        assert_eq!(Memo::eval(&d, &input), expected);
+        assert_eq!(input.1.get(), 6);
+
+        input.1.set(0);
+        assert_eq!(Memo::eval(&e, &input), expected);
+        assert_eq!(input.1.get(), 4);
+
+        // no caching outside of `Memo`
+        input.1.set(0);
+        assert_eq!(e.eval_with(&input), expected);
+        assert_eq!(input.1.get(), 6);
+    }
+
+    // other input at the same address
+    let mut input = [0.9, 0.9, 0.1].map(Membership::new);
+    assert_eq!(Memo::eval(&e, &input), e.eval_with(&input));
+    input = [0.1, 0.0, 0.1].map(Membership::new);
+    assert_eq!(Memo::eval(&e, &input), e.eval_with(&input));
+
+    // columns fetched once per batch
+    let cols = CountingCols(
+        vec![vec![Membership::new(0.2); 5], vec![Membership::new(0.7); 5]],
+        Cell::new(0)
+    );
+    let cached = ExprShared::cached(x | y);
+    let e = (cached.clone() & x) | (cached & !y);
+    let mut out = [Membership::new(0.0); 5];
+    e.eval_batch(&cols, &mut out);
+    assert_eq!(cols.1.replace(0), 6);
+    let mut memo = [Membership::new(0.0); 5];
+    Memo::eval_batch(&e, &cols, &mut memo);
+    assert_eq!(cols.1.get(), 4);
+    assert_eq!(memo, out);
+
+    let a = Expr::<Hamacher1>::new(0.3).with_tag("a");
+    let c = ExprShared::cached(a & !a);
+    let d = c.clone() | c;
+    assert_eq!(d.to_value(), ((a & !a) | (a & !a)).to_value());
+}
+
+#[test]
+fn batch() {
+    let rows = 57;
+    let col = |k: usize| -> Vec<Membership<Hamacher2>> {
+        (0..rows)
+            .map(|i| Membership::new(((i * k + 3) % 17) as f64 / 16.0))
+            .collect()
+    };
+    let cols = [col(1), col(5), col(7)];
+
+    let a = Expr::<Hamacher2>::new(0.3).with_tag("a");
+    let x = Expr::var(0usize);
+    let y = Expr::var(1usize);
+    let z = Expr::var(2usize);
+    let shared = ExprShared::cached(x | !y);
+    let hedged = ExprHedge::new(z, Hedge::Somewhat);
+    let weight = Weight::Or(Membership::new(0.7));
+    let d = Expr::all((shared.clone(), hedged, !a))
+        | ExprWeighted::new(Expr::any([x, y, z]), weight)
+        | ExprOneOf3::select(1, || x, || shared, || y);
+
+    let out = d.to_batch(&cols, rows);
+    for (i, &val) in out.iter().enumerate() {
+        let input = [cols[0][i], cols[1][i], cols[2][i]];
+        assert_eq!(val, d.eval_with(&input));
+    }
+
+    // fewer rows than columns have, columns by name
+    let mut named = HashMap::new();
+    named.insert("x", &cols[0][..]);
+    named.insert("y", &cols[1][..]);
+    let e = Expr::<Hamacher2>::var("x") & !Expr::var("y");
+    let mut out = vec![Membership::new(0.0); 10];
+    e.eval_batch(&named, &mut out);
+    for (i, &val) in out.iter().enumerate() {
+        let input: HashMap<_, _> = vec![("x", cols[0][i]), ("y", cols[1][i])]
+            .into_iter()
+            .collect();
+        assert_eq!(val, e.eval_with(&input));
+    }
+
+    // columns in a tuple
+    let e = Expr::<Hamacher2>::var(Index::<0>) | Expr::var(Index::<1>);
+    let out = e.to_batch(&(&cols[2], cols[0].clone()), rows);
+    assert_eq!(out[5], cols[2][5] | cols[0][5]);
+}
+
+#[test]
+fn compile() {
+    type Input = [Membership<Hamacher2>];
+    let x = || ExprDyn::<Hamacher2, Input>::new(Expr::var(0usize));
+    let y = || ExprDyn::<Hamacher2, Input>::new(Expr::var(1usize));
+    let a = Expr::<Hamacher2>::new(0.3).with_tag("a");
+    let weight = Weight::And(Membership::new(0.4));
+
+    // constant leaves and subtrees are folded
+    let d = (x() & ExprDyn::new(!a)) | ExprDyn::new(a.weighted_and(0.4))
+        | ExprDyn::new(ExprWeighted::new(Expr::var(1usize), weight));
+    let program = d.compile();
+    assert_eq!(program.instrs().len(), 7);
+    assert_eq!(program.depth(), 2);
+
+    // deeper than the stack kept inline
+    let mut e = x();
+    for i in 0..20 {
+        e = if i % 2 == 0 { y() & !e } else { x() | e };
+        e = !x() | e;
+    }
+    let program = e.compile();
+    assert!(program.depth() > 16);
+
+    for raw in [[0.1, 0.2], [0.6, 0.5], [1.0, 0.0]] {
+        let input = raw.map(Membership::new);
+        assert_eq!(d.compile().eval_with(&input[..]), d.eval_with(&input[..]));
+        assert_eq!(program.eval_with(&input[..]), e.eval_with(&input[..]));
+    }
+
+    let c = ExprDyn::<Hamacher2>::new(!a | (a & Expr::new(0.8)));
+    assert_eq!(c.compile().to_string(), format!("const {}", c.to_value()));
+    assert_eq!(c.compile().to_value(), c.to_value());
+}
@@ -17,25 +17,20 @@ pub struct ExprValue<S: Opset> {
 
 impl<S: Opset> ExprValue<S> {
     /// Creates from raw value. Panics if it's not a valid membership.
-    pub fn new(raw: Raw) -> Self {
-        Self {
-            membership: Membership::new(raw),
-            phantom: PhantomData
-        }
+    pub const fn new(raw: Raw) -> Self {
+        Self::from_membership(Membership::new(raw))
     }
 
     /// Creates from raw value.
-    pub fn try_new(raw: Raw) -> Option<Self> {
-        Membership::try_new(raw).map(|raw| {
-            Self {
-                membership: raw,
-                phantom: PhantomData
-            }
-        })
+    pub const fn try_new(raw: Raw) -> Option<Self> {
+        match Membership::try_new(raw) {
+            Some(membership) => Some(Self::from_membership(membership)),
+            None => None
+        }
     }
 
     /// Creates from valid membership.
-    pub fn from_membership(membership: Membership<S>) -> Self {
+    pub const fn from_membership(membership: Membership<S>) -> Self {
         Self {
             membership,
             phantom: PhantomData
@@ -56,7 +51,29 @@ impl<S: Opset> Debug for ExprValue<S> {
 
 impl<S: Opset> Display for ExprValue<S> {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        write!(f, "{}", self.membership.to_string())
+        write!(f, "{}", self.membership)
+    }
+}
+
+impl<S: Opset> ExprFormat for ExprValue<S> {
+    fn precedence(&self) -> Precedence {
+        Precedence::Atom
+    }
+
+    fn fmt_with(&self, _fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.membership)
+    }
+}
+
+impl<S: Opset, C: ?Sized> ExprVisit<S, C> for ExprValue<S> {
+    fn visit(&self, _ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        visitor.value(self.membership);
+    }
+}
+
+impl<S: Opset, C: ?Sized> ExprTerm<S, C> for ExprValue<S> {
+    fn to_term(&self) -> Term<S, C> {
+        Term::Const(self.membership)
     }
 }
 
@@ -67,6 +84,20 @@ impl<S: Opset> Expr<S> for ExprValue<S> {
     }
 }
 
+impl<S: Opset, C: ?Sized> ExprWith<S, C> for ExprValue<S> {
+    #[inline]
+    fn eval_with(&self, _ctx: &C) -> Membership<S> {
+        self.membership
+    }
+}
+
+impl<S: Opset, B: ?Sized> ExprBatch<S, B> for ExprValue<S> {
+    #[inline]
+    fn eval_batch(&self, _cols: &B, out: &mut [Membership<S>]) {
+        out.fill(self.membership);
+    }
+}
+
 impl_fuzzy_expr_ops! {
     ExprValue<S>
 }
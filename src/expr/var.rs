@@ -0,0 +1,284 @@
+use std::any::Any;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::{Membership, Value};
+use crate::impl_fuzzy_expr_ops;
+
+
+/// Input context providing memberships of variables.
+///
+/// Variable `I` is an index: `usize` for arrays, slices and `Vec`s,
+/// `Index<N>` for tuples, key for maps (which panic on missing keys)
+/// or any user type (e.g. a zero-cost tag) for custom structs:
+///
+/// ```
+/// # use fuzzy_systems::{Context, Expr, ExprWith, Membership, Opset, Yager1};
+/// # use fuzzy_systems::tags::{TagA, TagB};
+/// struct Input {
+///     a: f64,
+///     b: f64
+/// }
+///
+/// impl<S: Opset> Context<S, TagA> for Input {
+///     fn get(&self, _idx: &TagA) -> Membership<S> {
+///         Membership::new(self.a)
+///     }
+/// }
+///
+/// impl<S: Opset> Context<S, TagB> for Input {
+///     fn get(&self, _idx: &TagB) -> Membership<S> {
+///         Membership::new(self.b)
+///     }
+/// }
+///
+/// let rule = Expr::<Yager1>::var(TagA) & !Expr::var(TagB);
+/// assert_eq!(rule.to_string(), "(a & !b)");
+///
+/// let ctx = Input { a: 0.8, b: 0.5 };
+/// assert!((rule.eval_with(&ctx).as_raw() - 0.3).abs() < 0.001);
+/// ```
+pub trait Context<S: Opset, I> {
+    /// Membership of a variable.
+    fn get(&self, idx: &I) -> Membership<S>;
+}
+
+/// Type-level index of a tuple element.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct Index<const N: usize>;
+
+impl<const N: usize> Display for Index<N> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "x{}", N)
+    }
+}
+
+impl<S: Opset, V: Value<S>> Context<S, usize> for [V] {
+    #[inline]
+    fn get(&self, idx: &usize) -> Membership<S> {
+        self[*idx].membership()
+    }
+}
+
+impl<S: Opset, V: Value<S>, const N: usize> Context<S, usize> for [V; N] {
+    #[inline]
+    fn get(&self, idx: &usize) -> Membership<S> {
+        self[*idx].membership()
+    }
+}
+
+impl<S: Opset, V: Value<S>> Context<S, usize> for Vec<V> {
+    #[inline]
+    fn get(&self, idx: &usize) -> Membership<S> {
+        self[*idx].membership()
+    }
+}
+
+impl<S, K, V, I, H> Context<S, I> for HashMap<K, V, H>
+where
+    S: Opset,
+    K: Borrow<I> + Hash + Eq,
+    V: Value<S>,
+    I: Hash + Eq,
+    H: BuildHasher
+{
+    #[inline]
+    fn get(&self, idx: &I) -> Membership<S> {
+        self[idx].membership()
+    }
+}
+
+impl<S, K, V, I> Context<S, I> for BTreeMap<K, V>
+where
+    S: Opset,
+    K: Borrow<I> + Ord,
+    V: Value<S>,
+    I: Ord
+{
+    #[inline]
+    fn get(&self, idx: &I) -> Membership<S> {
+        self[idx].membership()
+    }
+}
+
+impl<S: Opset, I, C: Context<S, I> + ?Sized> Context<S, I> for &C {
+    #[inline]
+    fn get(&self, idx: &I) -> Membership<S> {
+        (**self).get(idx)
+    }
+}
+
+// Uninhabited context providing any variable, so `ExprWith<S, !>`
+// bounds any expression of the opset, regardless of its variables.
+impl<S: Opset, I> Context<S, I> for ! {
+    fn get(&self, _idx: &I) -> Membership<S> {
+        *self
+    }
+}
+
+/// Implement `Context` for tuples, indexed by `Index<N>`.
+macro_rules! impl_tuple_context {
+    ($( ($($n:tt: $v:ident),+) )*) => {
+        $( impl_tuple_context!(@elem [$($v),+] $($n $v),+); )*
+    };
+
+    (@elem [$($all:ident),+] $n:tt $v:ident $(, $rn:tt $rv:ident)*) => {
+        impl<S, $($all),+> Context<S, Index<$n>> for ($($all,)+)
+        where
+            S: Opset,
+            $($all: Value<S>),+
+        {
+            #[inline]
+            fn get(&self, _idx: &Index<$n>) -> Membership<S> {
+                self.$n.membership()
+            }
+        }
+
+        impl_tuple_context!(@elem [$($all),+] $($rn $rv),*);
+    };
+
+    (@elem [$($all:ident),+]) => {};
+}
+
+impl_tuple_context! {
+    (0: V0)
+    (0: V0, 1: V1)
+    (0: V0, 1: V1, 2: V2)
+    (0: V0, 1: V1, 2: V2, 3: V3)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4, 5: V5)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4, 5: V5, 6: V6)
+    (0: V0, 1: V1, 2: V2, 3: V3, 4: V4, 5: V5, 6: V6, 7: V7)
+}
+
+
+/// Fuzzy variable expression, looked up in the context on evaluation.
+///
+/// Unlike `ExprValue`, the same tree can be evaluated repeatedly
+/// with different inputs:
+///
+/// ```
+/// # use fuzzy_systems::{Expr, ExprWith, Hamacher1, Opset};
+/// let rule = Expr::<Hamacher1>::var(0) | Expr::var(1);
+/// assert_eq!(rule.to_string(), "(0 | 1)");
+///
+/// for input in &[[0.1, 0.2], [0.5, 0.5], [0.9, 0.0]] {
+///     let input = input.map(Hamacher1::member);
+///     assert!(rule.eval_with(&input) >= input[0]);
+/// }
+/// ```
+pub struct ExprVar<S: Opset, I> {
+    idx: I,
+    phantom: PhantomData<S>
+}
+
+impl<S: Opset, I> ExprVar<S, I> {
+    /// Creates from variable index.
+    pub fn new(idx: I) -> Self {
+        Self {
+            idx,
+            phantom: PhantomData
+        }
+    }
+
+    /// Variable index.
+    pub fn idx(&self) -> &I {
+        &self.idx
+    }
+}
+
+impl<S: Opset, I> Clone for ExprVar<S, I>
+where
+    I: Clone
+{
+    fn clone(&self) -> Self {
+        Self::new(self.idx.clone())
+    }
+}
+
+impl<S: Opset, I> Copy for ExprVar<S, I>
+where
+    I: Copy
+{}
+
+impl<S: Opset, I: Debug> Debug for ExprVar<S, I> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "ExprVar({:?})", self.idx)
+    }
+}
+
+impl<S: Opset, I: Display> Display for ExprVar<S, I> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.idx)
+    }
+}
+
+impl<S: Opset, I: Display> ExprFormat for ExprVar<S, I> {
+    fn precedence(&self) -> Precedence {
+        Precedence::Atom
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        fmt.fmt_var(f, &self.idx)
+    }
+}
+
+impl<S, I, C> ExprVisit<S, C> for ExprVar<S, I>
+where
+    S: Opset,
+    I: Display,
+    C: Context<S, I> + ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        let val = ctx.map(|ctx| ctx.get(&self.idx));
+        visitor.var(&self.idx, val);
+    }
+}
+
+impl<S, I, C> ExprTerm<S, C> for ExprVar<S, I>
+where
+    S: Opset + 'static,
+    I: Display + Debug + Clone + PartialEq + 'static,
+    C: Context<S, I> + ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::leaf(self.clone())
+    }
+
+    fn same_leaf(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| self.idx == other.idx)
+    }
+}
+
+impl<S, I, C> ExprWith<S, C> for ExprVar<S, I>
+where
+    S: Opset,
+    C: Context<S, I> + ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        ctx.get(&self.idx)
+    }
+}
+
+impl<S, I, B> ExprBatch<S, B> for ExprVar<S, I>
+where
+    S: Opset,
+    B: Columns<S, I> + ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        let rows = out.len();
+        out.copy_from_slice(&cols.column(&self.idx)[..rows]);
+    }
+}
+
+impl_fuzzy_expr_ops! {
+    ExprVar<S, I>
+}
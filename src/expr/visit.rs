@@ -0,0 +1,235 @@
+use std::fmt::Display;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+
+
+/// Object-safe visitor of expression nodes.
+///
+/// Nodes are visited in post-order (operands before their operator),
+/// transparent nodes (e.g. `ExprEither`, `ExprDyn`) are not visited
+/// themselves. See `Fold` for a more convenient interface.
+pub trait Visitor<S: Opset> {
+    /// Visits value leaf.
+    fn value(&mut self, val: Membership<S>);
+
+    /// Visits tagged value leaf.
+    fn tagged(&mut self, tag: &dyn Display, val: Membership<S>);
+
+    /// Visits variable leaf, with its value if the context is given.
+    fn var(&mut self, idx: &dyn Display, val: Option<Membership<S>>);
+
+    /// Visits negation of the last visited operand.
+    fn not(&mut self);
+
+    /// Visits conjunction of the two last visited operands.
+    fn and(&mut self);
+
+    /// Visits alternative of the two last visited operands.
+    fn or(&mut self);
+
+    /// Visits modifier applied to the last visited operand.
+    /// Leaves the operand as it is by default.
+    fn apply(&mut self, op: &dyn Modifier<S>) {
+        let _ = op;
+    }
+}
+
+/// Unary operation on memberships, applied to an expression by nodes
+/// such as `ExprWeighted`.
+///
+/// Displays as its name along with any parameters.
+pub trait Modifier<S: Opset>: Display {
+    /// Modified value.
+    fn apply(&self, val: Membership<S>) -> Membership<S>;
+}
+
+/// Expression traversable by `Visitor`.
+///
+/// Implemented by all standard expressions. Variables are looked up
+/// in context `ctx` if given, any expression can be visited without
+/// context as `ExprVisit<S, !>`.
+pub trait ExprVisit<S: Opset, C: ?Sized> {
+    /// Visits the expression's nodes.
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>);
+}
+
+
+/// Bottom-up fold of expression trees.
+///
+/// Each node is folded from the results of its operands, e.g. leaves
+/// counting and depth:
+///
+/// ```
+/// # use std::fmt::Display;
+/// # use fuzzy_systems::{Expr, Fold, Hamacher1, Membership, Modifier, Opset};
+/// struct Stats;
+///
+/// impl<S: Opset> Fold<S> for Stats {
+///     type Output = (usize, usize);  // (leaves, depth)
+///
+///     fn value(&mut self, _val: Membership<S>) -> (usize, usize) {
+///         (1, 1)
+///     }
+///
+///     fn var(&mut self, _idx: &dyn Display, _val: Option<Membership<S>>)
+///         -> (usize, usize) {
+///
+///         (1, 1)
+///     }
+///
+///     fn not(&mut self, val: (usize, usize)) -> (usize, usize) {
+///         (val.0, val.1 + 1)
+///     }
+///
+///     fn and(&mut self, lhs: (usize, usize), rhs: (usize, usize))
+///         -> (usize, usize) {
+///
+///         (lhs.0 + rhs.0, lhs.1.max(rhs.1) + 1)
+///     }
+///
+///     fn or(&mut self, lhs: (usize, usize), rhs: (usize, usize))
+///         -> (usize, usize) {
+///
+///         (lhs.0 + rhs.0, lhs.1.max(rhs.1) + 1)
+///     }
+///
+///     fn apply(&mut self, _op: &dyn Modifier<S>, val: (usize, usize))
+///         -> (usize, usize) {
+///
+///         (val.0, val.1 + 1)
+///     }
+/// }
+///
+/// let a = Expr::<Hamacher1>::new(0.1).with_tag("a");
+/// let b = Expr::new(0.6);
+/// let c = Expr::var("c");
+/// let d = (a | b) & !!c;
+/// assert_eq!(Fold::<Hamacher1>::fold(&mut Stats, &d), (3, 4));
+/// ```
+pub trait Fold<S: Opset> {
+    /// Result of folding a node.
+    type Output;
+
+    /// Folds value leaf.
+    fn value(&mut self, val: Membership<S>) -> Self::Output;
+
+    /// Folds tagged value leaf. Same as a value leaf by default.
+    fn tagged(&mut self, tag: &dyn Display, val: Membership<S>)
+        -> Self::Output {
+
+        let _ = tag;
+        self.value(val)
+    }
+
+    /// Folds variable leaf, with its value if the context is given.
+    fn var(&mut self, idx: &dyn Display, val: Option<Membership<S>>)
+        -> Self::Output;
+
+    /// Folds negation.
+    fn not(&mut self, val: Self::Output) -> Self::Output;
+
+    /// Folds conjunction.
+    fn and(&mut self, lhs: Self::Output, rhs: Self::Output) -> Self::Output;
+
+    /// Folds alternative.
+    fn or(&mut self, lhs: Self::Output, rhs: Self::Output) -> Self::Output;
+
+    /// Folds modifier application. Same as the operand by default.
+    fn apply(&mut self, op: &dyn Modifier<S>, val: Self::Output)
+        -> Self::Output {
+
+        let _ = op;
+        val
+    }
+
+    /// Folds expression without context.
+    fn fold<E>(&mut self, expr: &E) -> Self::Output
+    where
+        Self: Sized,
+        E: ExprVisit<S, !> + ?Sized
+    {
+        self.fold_impl(expr, None)
+    }
+
+    /// Folds expression with its variables looked up in the context.
+    fn fold_with<C, E>(&mut self, expr: &E, ctx: &C) -> Self::Output
+    where
+        Self: Sized,
+        C: ?Sized,
+        E: ExprVisit<S, C> + ?Sized
+    {
+        self.fold_impl(expr, Some(ctx))
+    }
+
+    #[doc(hidden)]
+    fn fold_impl<C, E>(&mut self, expr: &E, ctx: Option<&C>) -> Self::Output
+    where
+        Self: Sized,
+        C: ?Sized,
+        E: ExprVisit<S, C> + ?Sized
+    {
+        let mut folder = Folder {
+            fold: self,
+            stack: Vec::new()
+        };
+        expr.visit(ctx, &mut folder);
+        folder.pop()
+    }
+}
+
+
+/// Visitor folding nodes on a stack of operands.
+struct Folder<'a, F: Fold<S>, S: Opset> {
+    fold: &'a mut F,
+    stack: Vec<F::Output>
+}
+
+impl<'a, F: Fold<S>, S: Opset> Folder<'a, F, S> {
+    fn pop(&mut self) -> F::Output {
+        self.stack.pop().expect("Invalid state!")
+    }
+}
+
+impl<'a, F: Fold<S>, S: Opset> Visitor<S> for Folder<'a, F, S> {
+    fn value(&mut self, val: Membership<S>) {
+        let out = self.fold.value(val);
+        self.stack.push(out);
+    }
+
+    fn tagged(&mut self, tag: &dyn Display, val: Membership<S>) {
+        let out = self.fold.tagged(tag, val);
+        self.stack.push(out);
+    }
+
+    fn var(&mut self, idx: &dyn Display, val: Option<Membership<S>>) {
+        let out = self.fold.var(idx, val);
+        self.stack.push(out);
+    }
+
+    fn not(&mut self) {
+        let val = self.pop();
+        let out = self.fold.not(val);
+        self.stack.push(out);
+    }
+
+    fn and(&mut self) {
+        let rhs = self.pop();
+        let lhs = self.pop();
+        let out = self.fold.and(lhs, rhs);
+        self.stack.push(out);
+    }
+
+    fn or(&mut self) {
+        let rhs = self.pop();
+        let lhs = self.pop();
+        let out = self.fold.or(lhs, rhs);
+        self.stack.push(out);
+    }
+
+    fn apply(&mut self, op: &dyn Modifier<S>) {
+        let val = self.pop();
+        let out = self.fold.apply(op, val);
+        self.stack.push(out);
+    }
+}
@@ -0,0 +1,198 @@
+use std::any::Any;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+use super::*;
+
+use crate::opset::Opset;
+use crate::value::Membership;
+use crate::impl_fuzzy_expr_ops;
+
+
+/// Importance weight of an operand, see `ExprWeighted`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Weight<S: Opset> {
+    /// Weight within a conjunction: `max(1 - w, a)`.
+    And(Membership<S>),
+    /// Weight within an alternative: `min(w, a)`.
+    Or(Membership<S>)
+}
+
+impl<S: Opset> Weight<S> {
+    /// Weight value.
+    pub fn weight(self) -> Membership<S> {
+        match self {
+            Weight::And(weight) | Weight::Or(weight) => weight
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Weight::And(_) => "weighted_and",
+            Weight::Or(_) => "weighted_or"
+        }
+    }
+}
+
+impl<S: Opset> Display for Weight<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}({})", self.name(), self.weight())
+    }
+}
+
+impl<S: Opset> Modifier<S> for Weight<S> {
+    #[inline]
+    fn apply(&self, val: Membership<S>) -> Membership<S> {
+        match *self {
+            Weight::And(weight) => S::and_weighted([(val, weight)]),
+            Weight::Or(weight) => S::or_weighted([(val, weight)])
+        }
+    }
+}
+
+
+/// Fuzzy expression weighted by its importance.
+///
+/// Meant as an operand of a conjunction (`weighted_and`) or alternative
+/// (`weighted_or`). Weight `1` keeps the operand as it is, weight `0`
+/// makes it neutral, i.e. `1` and `0` respectively, regardless of the
+/// operand's value:
+///
+/// ```
+/// # use fuzzy_systems::{Expr, Hamacher1};
+/// let a = Expr::<Hamacher1>::new(0.2).with_tag("a");
+/// let b = Expr::new(0.9).with_tag("b");
+///
+/// // "a" matters half as much as "b"
+/// let d = a.weighted_and(0.5) & b;
+/// assert_eq!(d.to_string(), "(weighted_and(a, 0.5) & b)");
+/// assert!((d.to_value().as_raw() - 0.45).abs() < 1e-6);
+///
+/// let d = a.weighted_or(0.0) | b;
+/// assert_eq!(d.to_value(), b.to_value());
+/// ```
+pub struct ExprWeighted<S: Opset, E> {
+    expr: E,
+    weight: Weight<S>
+}
+
+impl<S: Opset, E> ExprWeighted<S, E> {
+    pub fn new(expr: E, weight: Weight<S>) -> Self {
+        Self {
+            expr,
+            weight
+        }
+    }
+
+    /// Weighted expression.
+    pub fn expr(&self) -> &E {
+        &self.expr
+    }
+
+    /// Weight of the expression.
+    pub fn weight(&self) -> Weight<S> {
+        self.weight
+    }
+}
+
+impl<S: Opset, E: Clone> Clone for ExprWeighted<S, E> {
+    fn clone(&self) -> Self {
+        Self::new(self.expr.clone(), self.weight)
+    }
+}
+
+impl<S: Opset, E: Copy> Copy for ExprWeighted<S, E> {}
+
+impl<S: Opset, E: Debug> Debug for ExprWeighted<S, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "ExprWeighted({:?}, {:?})", self.expr, self.weight)
+    }
+}
+
+impl<S: Opset, E: ExprFormat> Display for ExprWeighted<S, E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.fmt_with(&ExprFormatter::new(), f)
+    }
+}
+
+impl<S: Opset, E: ExprFormat> ExprFormat for ExprWeighted<S, E> {
+    fn precedence(&self) -> Precedence {
+        Precedence::Atom
+    }
+
+    fn fmt_with(&self, fmt: &ExprFormatter, f: &mut Formatter) -> FmtResult {
+        let weight = self.weight.weight();
+        fmt.fmt_call(f, self.weight.name(), &self.expr, &[&weight])
+    }
+}
+
+impl<S, E, C> ExprVisit<S, C> for ExprWeighted<S, E>
+where
+    S: Opset,
+    E: ExprVisit<S, C>,
+    C: ?Sized
+{
+    fn visit(&self, ctx: Option<&C>, visitor: &mut dyn Visitor<S>) {
+        self.expr.visit(ctx, visitor);
+        visitor.apply(&self.weight);
+    }
+}
+
+impl<S, E, C> ExprTerm<S, C> for ExprWeighted<S, E>
+where
+    S: Opset + 'static,
+    E: DynExpr<S, C> + Clone + 'static,
+    C: ?Sized
+{
+    fn to_term(&self) -> Term<S, C> {
+        Term::leaf(self.clone())
+    }
+
+    fn same_leaf(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.weight == other.weight
+                && self.expr.to_term().same(&other.expr.to_term())
+        })
+    }
+}
+
+impl<S, E> Expr<S> for ExprWeighted<S, E>
+where
+    S: Opset,
+    E: Expr<S>
+{
+    #[inline]
+    fn to_value(&self) -> Membership<S> {
+        self.weight.apply(self.expr.to_value())
+    }
+}
+
+impl<S, E, C> ExprWith<S, C> for ExprWeighted<S, E>
+where
+    S: Opset,
+    E: ExprWith<S, C>,
+    C: ?Sized
+{
+    #[inline]
+    fn eval_with(&self, ctx: &C) -> Membership<S> {
+        self.weight.apply(self.expr.eval_with(ctx))
+    }
+}
+
+impl<S, E, B> ExprBatch<S, B> for ExprWeighted<S, E>
+where
+    S: Opset,
+    E: ExprBatch<S, B>,
+    B: ?Sized
+{
+    #[inline]
+    fn eval_batch(&self, cols: &B, out: &mut [Membership<S>]) {
+        self.expr.eval_batch(cols, out);
+        for val in out {
+            *val = self.weight.apply(*val);
+        }
+    }
+}
+
+impl_fuzzy_expr_ops! {
+    ExprWeighted<S, E>
+}
@@ -65,6 +65,11 @@
 //! ```
 //!
 //!
+//! ## Optional features
+//!  * `rayon`: parallel evaluation and fuzzification of large input
+//!    collections, see the `par` module
+//!
+//!
 //! ## What makes it different
 //! There are a few other fuzzy systems libraries out there,
 //! particularly
@@ -80,34 +85,38 @@
 //!  * `rsfuzzy` and `fuzzy_logic` use string-identified variables,
 //!    `fuzzy-reasoning-mamdani` uses strongly-typed domains with
 //!    string-identified states,
-//!    `fuzzy_systems` has no concept of a variable (although a value can
-//!    be tagged with string name)
+//!    `fuzzy_systems` variables are indexed statically (by tuple position
+//!    or a custom type) and a value can be tagged with string name
 //!  * all others can fail in runtime due to non-existing
 //!    variables or states, `fuzzy_systems` does its transformations directly
-//!    on Rust struct so it can't fail
+//!    on Rust struct so it can't fail, only rules parsed at runtime
+//!    (`ExprDyn::parse`) can, returning `ParseError`
 //!  * all others operate directly on membership values,
 //!    `fuzzy_systems` can assign membership to any Rust value (via
 //!    `Value<S>` trait)
 //!  * `rsfuzzy` and `fuzzy-reasoning-mamdani` have fixed fuzzy operation
-//!     system, `fuzzy_logic` and `fuzzy_systems` enable user to pick one as
-//!     well as create a custom one
+//!    system, `fuzzy_logic` and `fuzzy_systems` enable user to pick one as
+//!    well as create a custom one
 //! 
 //! ### Compared to rsfuzzy
-//!  * `rsfuzzy` does string rule parsing, `fuzzy_systems.rs` does not
+//!  * `rsfuzzy` does string rule parsing, `fuzzy_systems` does it only
+//!    on demand (into dynamic expressions)
 //!  * `rsfuzzy` has fixed fuzzy operation system, `fuzzy_systems` enables user
 //!    to pick one of many as well as create a custom one
 //! 
 //! ### Compared to fuzzy_logic
-//!  * `fuzzy_logic` defines sets and sets operations, `fuzzy_systems`
-//!    does not
+//!  * both define sets and sets operations, `fuzzy_systems` performs
+//!    them with the same opsets as expressions (see `set` module)
 //!  * `fuzzy_logic` binds operation sets to a rule, `fuzzy_systems`
 //!    binds it directly to expression
 //!  * `fuzzy_logic` doesn't provide easy-to-use fuzzy expressions creation
 //!    methods, `fuzzy_systems` does (via methods, binary operators and
 //!    `fuzzy_math!` macro)
 //!  * `fuzzy_logic` heap-allocates (in `Box`, `HashMap` etc),
-//!    `fuzzy_systems` does not
-//!  * `fuzzy_logic` caches results, `fuzzy_systems` does not
+//!    `fuzzy_systems` static expressions do not, only the opt-in
+//!    `ExprDyn` boxes its nodes
+//!  * `fuzzy_logic` caches results, `fuzzy_systems` caches them only
+//!    on demand (via `ExprShared`)
 //!  * `fuzzy_logic` uses `f32`, `fuzzy_systems` uses `f64`
 //! 
 //! ### Compared to fuzzy-reasoning-mamdani
@@ -115,17 +124,17 @@
 //!  * `fuzzy-reasoning-mamdani` uses `(bool, f64)` for fuzzy values,
 //!    `fuzzy_systems` uses `f64`
 
-#![cfg_attr(feature="clippy", feature(plugin))]
-#![cfg_attr(feature="clippy", plugin(clippy))]
-
-#![feature(fn_traits)] 
 #![feature(never_type)]
-#![feature(trait_alias)]
+#![feature(portable_simd)]
+#![cfg_attr(test, feature(trait_alias))]
 
 
 pub mod expr;
 pub mod opset;
 mod value;
+pub mod set;
+#[cfg(feature = "rayon")]
+pub mod par;
 
 pub use self::expr::*;
 pub use self::expr::tags;
@@ -133,3 +142,8 @@ pub use self::expr::tags;
 pub use self::opset::*;
 
 pub use self::value::*;
+
+pub use self::set::{
+    ContinuousFuzzySet, DiscreteFuzzySet, FuzzyNumber, LevelSets, LrShape,
+    SampledFuzzySet
+};
@@ -9,9 +9,10 @@
 mod macros;
 mod traits;
 mod list;
+mod tabulated;
 #[cfg(test)]
 mod test;
 
-pub use self::macros::*;
 pub use self::traits::*;
 pub use self::list::*;
+pub use self::tabulated::{ErrorBound, Tabulated};
@@ -1,42 +1,82 @@
-use super::Opset;
-
-use crate::value::Membership;
+use super::OpsetLaws;
 
 
 fuzzy_opset! {
     /// Yager fuzzy operation set with w = 1.
-    pub Yager1 {
+    pub const simd Yager1 {
          ~x   = 1.0 - x;
         a | b = (a + b).min(1.0);
         a & b = (a + b).max(1.0) - 1.0;
     }
 
     /// Yager fuzzy operation set with w -> inf.
-    pub YagerInf {
+    pub const simd YagerInf {
          ~x   = 1.0 - x;
         a | b = a.max(b);
         a & b = a.min(b);
     }
 
     /// Hamacher fuzzy operation set with gamma = 0.
-    pub Hamacher0 {
+    pub const simd Hamacher0 {
          ~x   = 1.0 - x;
         a | b = (a + b - 2.0 * a * b) / (1.0 - a * b);
         a & b = (a * b) / (a + b - a * b);
     }
 
     /// Hamacher fuzzy operation set with gamma = 1.
-    pub Hamacher1 {
+    pub const simd Hamacher1 {
          ~x   = 1.0 - x;
         a | b = a + b - a * b;
         a & b = a * b;
     }
 
     /// Hamacher fuzzy operation set with gamma = 2.
-    pub Hamacher2 {
+    pub const simd Hamacher2 {
          ~x   = 1.0 - x;
         a | b = (a + b) / (1.0 + a * b);
         a & b = (a * b) / (2.0 - a - b - a * b);
     }
 }
 
+
+impl OpsetLaws for Yager1 {
+    const IDEMPOTENT: bool = false;
+    const INVOLUTIVE: bool = true;
+    const DE_MORGAN: bool = true;
+    const DISTRIBUTIVE: bool = false;
+    const BOUNDED: bool = true;
+}
+
+impl OpsetLaws for YagerInf {
+    const IDEMPOTENT: bool = true;
+    const INVOLUTIVE: bool = true;
+    const DE_MORGAN: bool = true;
+    const DISTRIBUTIVE: bool = true;
+    const BOUNDED: bool = true;
+}
+
+// `0 & 0` and `1 | 1` are undefined.
+impl OpsetLaws for Hamacher0 {
+    const IDEMPOTENT: bool = false;
+    const INVOLUTIVE: bool = true;
+    const DE_MORGAN: bool = true;
+    const DISTRIBUTIVE: bool = false;
+    const BOUNDED: bool = false;
+}
+
+impl OpsetLaws for Hamacher1 {
+    const IDEMPOTENT: bool = false;
+    const INVOLUTIVE: bool = true;
+    const DE_MORGAN: bool = true;
+    const DISTRIBUTIVE: bool = false;
+    const BOUNDED: bool = true;
+}
+
+// `&` as defined is not dual to `|` and has no identity element.
+impl OpsetLaws for Hamacher2 {
+    const IDEMPOTENT: bool = false;
+    const INVOLUTIVE: bool = true;
+    const DE_MORGAN: bool = false;
+    const DISTRIBUTIVE: bool = false;
+    const BOUNDED: bool = false;
+}
@@ -4,10 +4,43 @@
 /// in any of the two notations:
 ///   * function notation:  not(x) { ... }  and(a,b) { ... }
 ///   * operator notation:  ~x = ...;  a & b = ...;
+///
+/// Opsets declared `simd` (e.g. `pub simd Yager1 { ... }`) also evaluate
+/// their formulas on 8 lanes at once for `not_x8`, `or_x8` and `and_x8`,
+/// so the formulas may use arithmetic, `min`, `max`, `abs`, `sqrt`,
+/// `mul_add`, `powf`, `powi`, `exp` and `ln` only. Other opsets run
+/// those value by value and may use any `f64` expression:
+///
+/// ```
+/// # use fuzzy_systems::{fuzzy_opset, Membership, Opset};
+/// fuzzy_opset! {
+///     pub Drastic {
+///          ~x   = 1.0 - x;
+///         a | b = if a.min(b) == 0.0 { a.max(b) } else { 1.0 };
+///         a & b = if a.max(b) == 1.0 { a.min(b) } else { 0.0 };
+///     }
+/// }
+///
+/// let vals = [0.0, 0.3, 1.0, 0.5, 0.9, 0.1, 1.0, 0.7].map(Membership::new);
+/// let ones = [Membership::new(1.0); 8];
+/// assert_eq!(Drastic::and_x8(vals, ones), vals);
+/// ```
+///
+/// Opsets declared `const` (e.g. `pub const simd Yager1 { ... }`) also get
+/// inherent `const fn` versions of the operations, shadowing the trait's
+/// ones, so their formulas must be `const` too (arithmetic, `min`, `max`
+/// and `abs`):
+///
+/// ```
+/// # use fuzzy_systems::{Hamacher1, Membership};
+/// const A: Membership<Hamacher1> = Membership::new(0.5);
+/// const B: Membership<Hamacher1> = Hamacher1::and(A, Hamacher1::not(A));
+/// assert_eq!(B.as_raw(), 0.25);
+/// ```
 #[macro_export]
 macro_rules! fuzzy_opset {
 	( $( $spec:tt )* ) => {
-		parse_fuzzy_opset! {
+		$crate::parse_fuzzy_opset! {
 			meta: [],
 			spec: $( $spec )*
 		}
@@ -25,41 +58,100 @@ macro_rules! parse_fuzzy_opset {
         meta: [ $( #[$metas:meta] )* ],
         spec: #[$n_meta:meta] $( $tts:tt )+
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             meta: [ $( #[$metas] )* #[$n_meta] ],
             spec: $( $tts )+
         }
     };
 
-	// Parse name and public visibility...
+    // Parse modifiers...
     (
         meta: [ $( #[$metas:meta] )* ],
-        spec: pub $name:ident $( $rest:tt )*
+        spec: $( $tts:tt )+
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
+            vis:  [ ],
+            konst: [ ],
+            simd: [ ],
+            meta: [ $( #[$metas] )* ],
+            mods: $( $tts )+
+        }
+    };
+
+    // Parse public visibility...
+    (
+        vis:  [ ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
+        meta: [ $( #[$metas:meta] )* ],
+        mods: pub $( $rest:tt )+
+    ) => {
+        $crate::parse_fuzzy_opset! {
             vis:  [ pub ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
-			name: [ $name ],
-			spec: $( $rest )*
+            mods: $( $rest )+
         }
-	};
+    };
 
-	// Parse name and private visibility...
+    // Parse constness...
     (
+        vis:  [ $( $vis:ident )* ],
+        konst: [ ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
-        spec: $name:ident $( $rest:tt )*
+        mods: const $( $rest:tt )+
     ) => {
-        parse_fuzzy_opset! {
-            vis:  [ ],
+        $crate::parse_fuzzy_opset! {
+            vis:  [ $($vis)* ],
+            konst: [ const ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
-			name: [ $name ],
-            spec: $($rest)*
+            mods: $( $rest )+
         }
-	};
+    };
+
+    // Parse vectorisation...
+    (
+        vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ ],
+        meta: [ $( #[$metas:meta] )* ],
+        mods: simd $name:ident $( $rest:tt )*
+    ) => {
+        $crate::parse_fuzzy_opset! {
+            vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ simd ],
+            meta: [ $( #[$metas] )* ],
+            mods: $name $( $rest )*
+        }
+    };
+
+    // Parse name...
+    (
+        vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
+        meta: [ $( #[$metas:meta] )* ],
+        mods: $name:ident $( $rest:tt )*
+    ) => {
+        $crate::parse_fuzzy_opset! {
+            vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
+            meta: [ $( #[$metas] )* ],
+            name: [ $name ],
+            spec: $( $rest )*
+        }
+    };
 
     // Parse inner
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         spec: {
@@ -67,8 +159,10 @@ macro_rules! parse_fuzzy_opset {
         }
         $($rest:tt)*
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [] () {},
@@ -79,7 +173,7 @@ macro_rules! parse_fuzzy_opset {
             }
         }
 
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             meta: [ ],
             spec: $( $rest )*
         }
@@ -88,6 +182,8 @@ macro_rules! parse_fuzzy_opset {
     // Parse `not` short form.
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not: [] () {},
@@ -99,8 +195,10 @@ macro_rules! parse_fuzzy_opset {
             $($inner:tt)*
         }
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [ $(#[$met])* ] ( $arg ) { $impl },
@@ -115,6 +213,8 @@ macro_rules! parse_fuzzy_opset {
     // Parse `not` long form.
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not: [] () {},
@@ -126,8 +226,10 @@ macro_rules! parse_fuzzy_opset {
             $($inner:tt)*
         }
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [ $(#[$met])* ] ( $arg ) { $($impl)* },
@@ -142,6 +244,8 @@ macro_rules! parse_fuzzy_opset {
     // Parse `or` short form.
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not: [ $(#[$notmet:meta])* ] ( $($notarg:ident)*  ) { $($not:tt)* },
@@ -153,8 +257,10 @@ macro_rules! parse_fuzzy_opset {
             $($inner:tt)*
         }
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [ $(#[$notmet])* ] ( $($notarg)*  ) { $($not)*  },
@@ -169,6 +275,8 @@ macro_rules! parse_fuzzy_opset {
     // Parse `or` long form.
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not: [ $(#[$notmet:meta])* ] ( $($notarg:ident)*  ) { $($not:tt)* },
@@ -180,8 +288,10 @@ macro_rules! parse_fuzzy_opset {
             $($inner:tt)*
         }
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [ $(#[$notmet])* ] ( $($notarg)*  ) { $($not)*  },
@@ -196,6 +306,8 @@ macro_rules! parse_fuzzy_opset {
     // Parse `and` short form.
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not: [ $(#[$notmet:meta])* ] ( $($notarg:ident)*  ) { $($not:tt)* },
@@ -207,8 +319,10 @@ macro_rules! parse_fuzzy_opset {
             $($inner:tt)*
         }
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [ $(#[$notmet])* ] ( $($notarg)* ) { $($not)* },
@@ -223,6 +337,8 @@ macro_rules! parse_fuzzy_opset {
     // Parse `and` long form.
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not: [ $(#[$notmet:meta])* ] ( $($notarg:ident)*  ) { $($not:tt)* },
@@ -234,8 +350,10 @@ macro_rules! parse_fuzzy_opset {
             $($inner:tt)*
         }
     ) => {
-        parse_fuzzy_opset! {
+        $crate::parse_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [ $(#[$notmet])* ] ( $($notarg)* ) { $($not)* },
@@ -250,6 +368,8 @@ macro_rules! parse_fuzzy_opset {
     // Implement
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not: [ $(#[$notmet:meta])* ] ( $($notarg:ident)*  ) { $($not:tt)* },
@@ -257,8 +377,10 @@ macro_rules! parse_fuzzy_opset {
         and: [ $(#[$andmet:meta])* ] ( $($andarg:ident),* ) { $($and:tt)* },
         inner: {}
     ) => {
-        impl_fuzzy_opset! {
+        $crate::impl_fuzzy_opset! {
             vis:  [ $($vis)* ],
+            konst: [ $($konst)? ],
+            simd: [ $($simd)? ],
             meta: [ $( #[$metas] )* ],
 			name: [ $name ],
             not: [ $(#[$notmet])* ] ( $($notarg)*  ) { $($not)* },
@@ -273,6 +395,8 @@ macro_rules! parse_fuzzy_opset {
 macro_rules! impl_fuzzy_opset {
     (
 		vis:  [ $( $vis:ident )* ],
+        konst: [ $( $konst:tt )? ],
+        simd: [ $( $simd:tt )? ],
         meta: [ $( #[$metas:meta] )* ],
         name: [ $name:ident ],
         not:
@@ -292,30 +416,125 @@ macro_rules! impl_fuzzy_opset {
         #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
         $($vis)* struct $name {}
 
-        impl Opset for $name {
+        impl $crate::Opset for $name {
             $( #[$notmet] )*
-            fn not($not1: Membership<Self>) -> Membership<Self> {
+            fn not($not1: $crate::Membership<Self>)
+                -> $crate::Membership<Self> {
+
                 let $not1 = $not1.as_raw();
-                Membership::unchecked_new($($not)*)
+                $crate::Membership::unchecked_new($($not)*)
             }
 
             $( #[$ormet] )*
-            fn or($or1: Membership<Self>, $or2: Membership<Self>)
-                -> Membership<Self> {
-
+            fn or(
+                $or1: $crate::Membership<Self>,
+                $or2: $crate::Membership<Self>
+            ) -> $crate::Membership<Self> {
                 let $or1 = $or1.as_raw();
                 let $or2 = $or2.as_raw();
-                Membership::unchecked_new($($or)*)
+                $crate::Membership::unchecked_new($($or)*)
             }
 
             $( #[$andmet] )*
-            fn and($and1: Membership<Self>, $and2: Membership<Self>)
-                -> Membership<Self> {
+            fn and(
+                $and1: $crate::Membership<Self>,
+                $and2: $crate::Membership<Self>
+            ) -> $crate::Membership<Self> {
+                let $and1 = $and1.as_raw();
+                let $and2 = $and2.as_raw();
+                $crate::Membership::unchecked_new($($and)*)
+            }
+
+            $crate::impl_fuzzy_opset! {
+                @simd [ $($simd)? ]
+                not: ( $not1 ) { $($not)* },
+                or:  ( $or1, $or2 ) { $($or)* },
+                and: ( $and1, $and2 ) { $($and)* }
+            }
+        }
+
+        $crate::impl_fuzzy_opset! {
+            @const [ $($konst)? ] $name,
+            not: ( $not1 ) { $($not)* },
+            or:  ( $or1, $or2 ) { $($or)* },
+            and: ( $and1, $and2 ) { $($and)* }
+        }
+    };
+
+    (@simd [ ] $($rest:tt)*) => {};
+
+    (
+        @simd [ simd ]
+        not: ( $not1:ident ) { $($not:tt)* },
+        or:  ( $or1:ident, $or2:ident ) { $($or:tt)* },
+        and: ( $and1:ident, $and2:ident ) { $($and:tt)* }
+    ) => {
+        #[inline]
+        fn not_x8($not1: $crate::MembershipX8<Self>)
+            -> $crate::MembershipX8<Self> {
+
+            let $not1 = $crate::Lanes::from_members($not1);
+            $crate::Lanes::from($($not)*).to_members()
+        }
+
+        #[inline]
+        fn or_x8(
+            $or1: $crate::MembershipX8<Self>,
+            $or2: $crate::MembershipX8<Self>
+        ) -> $crate::MembershipX8<Self> {
+            let $or1 = $crate::Lanes::from_members($or1);
+            let $or2 = $crate::Lanes::from_members($or2);
+            $crate::Lanes::from($($or)*).to_members()
+        }
 
+        #[inline]
+        fn and_x8(
+            $and1: $crate::MembershipX8<Self>,
+            $and2: $crate::MembershipX8<Self>
+        ) -> $crate::MembershipX8<Self> {
+            let $and1 = $crate::Lanes::from_members($and1);
+            let $and2 = $crate::Lanes::from_members($and2);
+            $crate::Lanes::from($($and)*).to_members()
+        }
+    };
+
+    (@const [ ] $($rest:tt)*) => {};
+
+    (
+        @const [ const ] $name:ident,
+        not: ( $not1:ident ) { $($not:tt)* },
+        or:  ( $or1:ident, $or2:ident ) { $($or:tt)* },
+        and: ( $and1:ident, $and2:ident ) { $($and:tt)* }
+    ) => {
+        #[allow(dead_code)]
+        impl $name {
+            /// Same as `Opset::not`, usable in constants.
+            pub const fn not($not1: $crate::Membership<Self>)
+                -> $crate::Membership<Self> {
+
+                let $not1 = $not1.as_raw();
+                $crate::Membership::unchecked_new($($not)*)
+            }
+
+            /// Same as `Opset::or`, usable in constants.
+            pub const fn or(
+                $or1: $crate::Membership<Self>,
+                $or2: $crate::Membership<Self>
+            ) -> $crate::Membership<Self> {
+                let $or1 = $or1.as_raw();
+                let $or2 = $or2.as_raw();
+                $crate::Membership::unchecked_new($($or)*)
+            }
+
+            /// Same as `Opset::and`, usable in constants.
+            pub const fn and(
+                $and1: $crate::Membership<Self>,
+                $and2: $crate::Membership<Self>
+            ) -> $crate::Membership<Self> {
                 let $and1 = $and1.as_raw();
                 let $and2 = $and2.as_raw();
-                Membership::unchecked_new($($and)*)
+                $crate::Membership::unchecked_new($($and)*)
             }
         }
-    }
+    };
 }
@@ -0,0 +1,321 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::sync::RwLock;
+
+use super::Opset;
+
+use crate::value::{Membership, Raw};
+
+
+/// Opset `S` precomputed on an `N`×`N` grid, interpolated bilinearly.
+///
+/// Meant for opsets too expensive to evaluate (e.g. ones involving
+/// `powf` or `ln`), at the cost of accuracy, see `error_bound`.
+/// Values at grid nodes, including `0` and `1`, are exact. Nodes where
+/// `S` is undefined (`NaN`) take its limit from within the unit square,
+/// or else the value of the nearest defined node. Tables are built on
+/// first use, once per `S` and `N`, and shared by all threads:
+///
+/// ```
+/// # use fuzzy_systems::{Hamacher0, Membership, Opset, Tabulated};
+/// type Fast = Tabulated<Hamacher0, 65>;
+/// Fast::prepare();
+///
+/// let a = Membership::<Fast>::new(0.5);
+/// let b = Membership::new(0.25);
+/// let exact = Hamacher0::member(0.5) & Hamacher0::member(0.25);
+/// assert_eq!((a & b).as_raw(), exact.as_raw());
+///
+/// let error = Fast::error_bound(256);
+/// assert!(error.max() < 2e-3);
+/// assert!((Fast::member(0.0) & Fast::member(0.0)).as_raw() < 1e-6);
+/// assert!(Tabulated::<Hamacher0, 17>::error_bound(256).max() > error.max());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Tabulated<S: Opset, const N: usize> {
+    phantom: PhantomData<S>
+}
+
+impl<S: Opset + 'static, const N: usize> Tabulated<S, N> {
+    /// Builds the table unless already built, so the first operation
+    /// doesn't take long.
+    pub fn prepare() {
+        Self::table();
+    }
+
+    /// Largest deviations from the exact opset, measured on a grid
+    /// of `samples + 1` points per axis.
+    ///
+    /// Points where the exact opset is undefined (`NaN`) are skipped,
+    /// ones where only the table is count as infinite deviation.
+    /// A multiple of `N - 1` samples covers the grid nodes as well as
+    /// points between them.
+    pub fn error_bound(samples: usize) -> ErrorBound {
+        assert!(samples > 0);
+        let point = |k: usize| k as Raw / samples as Raw;
+        let error = |exact: Membership<S>, approx: Membership<Self>| {
+            let error = (exact.as_raw() - approx.as_raw()).abs();
+            if exact.is_nan() { 0.0 }
+            else if error.is_nan() { Raw::INFINITY }
+            else { error }
+        };
+
+        let mut bound = ErrorBound {
+            not: 0.0,
+            or: 0.0,
+            and: 0.0
+        };
+        for i in 0..=samples {
+            let a = point(i);
+            let (exact_a, approx_a) = (Membership::new(a), Membership::new(a));
+            bound.not = bound.not.max(error(S::not(exact_a), !approx_a));
+
+            for j in 0..=samples {
+                let b = point(j);
+                let (exact_b, approx_b) =
+                    (Membership::new(b), Membership::new(b));
+                let or = error(S::or(exact_a, exact_b), approx_a | approx_b);
+                let and = error(S::and(exact_a, exact_b), approx_a & approx_b);
+                bound.or = bound.or.max(or);
+                bound.and = bound.and.max(and);
+            }
+        }
+        bound
+    }
+
+    /// Table of the opset, built on first use.
+    #[inline]
+    fn table() -> &'static Table<S, N> {
+        shared(Table::new)
+    }
+}
+
+impl<S: Opset + 'static, const N: usize> Opset for Tabulated<S, N> {
+    #[inline]
+    fn not(val: Membership<Self>) -> Membership<Self> {
+        let table = Self::table();
+        Membership::unchecked_new(table.not(val.as_raw()))
+    }
+
+    #[inline]
+    fn or(lhs: Membership<Self>, rhs: Membership<Self>) -> Membership<Self> {
+        let table = Self::table();
+        Membership::unchecked_new(table.or(lhs.as_raw(), rhs.as_raw()))
+    }
+
+    #[inline]
+    fn and(lhs: Membership<Self>, rhs: Membership<Self>) -> Membership<Self> {
+        let table = Self::table();
+        Membership::unchecked_new(table.and(lhs.as_raw(), rhs.as_raw()))
+    }
+}
+
+
+/// Largest deviations of a `Tabulated` opset from the exact one.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ErrorBound {
+    not: Raw,
+    or: Raw,
+    and: Raw
+}
+
+impl ErrorBound {
+    /// Largest deviation of negation.
+    pub fn not(&self) -> Raw {
+        self.not
+    }
+
+    /// Largest deviation of alternative.
+    pub fn or(&self) -> Raw {
+        self.or
+    }
+
+    /// Largest deviation of conjunction.
+    pub fn and(&self) -> Raw {
+        self.and
+    }
+
+    /// Largest deviation of all operations.
+    pub fn max(&self) -> Raw {
+        self.not.max(self.or).max(self.and)
+    }
+}
+
+impl Display for ErrorBound {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "not: {:e}, or: {:e}, and: {:e}", self.not, self.or, self.and)
+    }
+}
+
+
+/// Table of any opset and size.
+type AnyTable = dyn Any + Send + Sync;
+
+/// Tables of all tabulated opsets, built so far.
+static TABLES: RwLock<BTreeMap<TypeId, &'static AnyTable>> =
+    RwLock::new(BTreeMap::new());
+
+/// Table of type `T`, built by `build` unless already built.
+///
+/// The table used last is cached per thread, so repeated operations
+/// of one opset take neither the lock, the map lookup nor a dynamic
+/// downcast.
+#[inline]
+fn shared<T, F>(build: F) -> &'static T
+where
+    T: Any + Send + Sync,
+    F: FnOnce() -> T
+{
+    thread_local! {
+        static LAST: Cell<Option<(TypeId, *const ())>> =
+            const { Cell::new(None) };
+    }
+
+    let id = TypeId::of::<T>();
+    match LAST.get() {
+        // SAFETY: pointers are cached along with the `TypeId` of their
+        // pointee, which is leaked, so it's a valid `&'static T`.
+        Some((key, ptr)) if key == id => unsafe { &*(ptr as *const T) },
+        _ => {
+            let table = lookup(id, || Box::leak(Box::new(build())));
+            let table: &'static T =
+                table.downcast_ref().expect("Invalid state!");
+            LAST.set(Some((id, table as *const T as *const ())));
+            table
+        }
+    }
+}
+
+/// Table of the given type, built by `build` unless already built.
+#[cold]
+fn lookup<F>(id: TypeId, build: F) -> &'static AnyTable
+where
+    F: FnOnce() -> &'static AnyTable
+{
+    let found = TABLES.read()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(&id)
+        .copied();
+    found.unwrap_or_else(|| {
+        let mut tables = TABLES.write().unwrap_or_else(|err| err.into_inner());
+        *tables.entry(id).or_insert_with(build)
+    })
+}
+
+
+/// Values of the operations at grid nodes `i / (N - 1)`.
+struct Table<S: Opset, const N: usize> {
+    not: Vec<Raw>,
+    or: Vec<Raw>,
+    and: Vec<Raw>,
+    phantom: PhantomData<fn() -> S>
+}
+
+impl<S: Opset, const N: usize> Table<S, N> {
+    fn new() -> Self {
+        const { assert!(N >= 2, "Table needs at least 2 nodes!") };
+        let node = |i: usize| i as Raw / (N - 1) as Raw;
+
+        let mut not: Vec<_> = (0..N)
+            .map(|i| limit(|x, _| S::not(x), node(i), 0.5))
+            .collect();
+        let mut or = Vec::with_capacity(N * N);
+        let mut and = Vec::with_capacity(N * N);
+        for i in 0..N {
+            for j in 0..N {
+                or.push(limit(S::or, node(i), node(j)));
+                and.push(limit(S::and, node(i), node(j)));
+            }
+        }
+        fill_undefined(&mut not, N);
+        fill_undefined(&mut or, N);
+        fill_undefined(&mut and, N);
+
+        Self {
+            not,
+            or,
+            and,
+            phantom: PhantomData
+        }
+    }
+
+    #[inline]
+    fn not(&self, x: Raw) -> Raw {
+        let (i, fx) = Self::locate(x);
+        self.not[i] * (1.0 - fx) + self.not[i + 1] * fx
+    }
+
+    #[inline]
+    fn or(&self, x: Raw, y: Raw) -> Raw {
+        Self::interpolate(&self.or, x, y)
+    }
+
+    #[inline]
+    fn and(&self, x: Raw, y: Raw) -> Raw {
+        Self::interpolate(&self.and, x, y)
+    }
+
+    /// Index of the cell containing the value and offset within it.
+    #[inline]
+    fn locate(x: Raw) -> (usize, Raw) {
+        let t = x * (N - 1) as Raw;
+        // saturating via `u32` takes fewer instructions than via `usize`
+        let i = (t as u32 as usize).min(N - 2);
+        (i, t - i as Raw)
+    }
+
+    #[inline]
+    fn interpolate(vals: &[Raw], x: Raw, y: Raw) -> Raw {
+        let (i, fx) = Self::locate(x);
+        let (j, fy) = Self::locate(y);
+        // rows `i` and `i + 1` from column `j` on, checked at once
+        let cell = &vals[i * N + j..][..N + 2];
+
+        let lo = cell[0] * (1.0 - fy) + cell[1] * fy;
+        let hi = cell[N] * (1.0 - fy) + cell[N + 1] * fy;
+        lo * (1.0 - fx) + hi * fx
+    }
+}
+
+
+/// Distance from a grid node, at which limits of operations are taken.
+const LIMIT_STEP: Raw = 1e-9;
+
+/// Value of the operation at the point, or where undefined (`NaN`),
+/// its limit from within the unit square.
+fn limit<S, F>(op: F, x: Raw, y: Raw) -> Raw
+where
+    S: Opset,
+    F: Fn(Membership<S>, Membership<S>) -> Membership<S>
+{
+    let inward = |x: Raw| x + (0.5 - x).signum() * LIMIT_STEP;
+    let val = op(Membership::new(x), Membership::new(y)).as_raw();
+    if val.is_nan() {
+        op(Membership::new(inward(x)), Membership::new(inward(y))).as_raw()
+    }
+    else {
+        val
+    }
+}
+
+/// Replaces values still undefined (`NaN`) by the nearest defined one,
+/// within rows of `cols` values.
+fn fill_undefined(vals: &mut [Raw], cols: usize) {
+    let defined: Vec<usize> = (0..vals.len())
+        .filter(|&k| !vals[k].is_nan())
+        .collect();
+    let dist = |a: usize, b: usize| {
+        (a / cols).abs_diff(b / cols).max((a % cols).abs_diff(b % cols))
+    };
+
+    for k in 0..vals.len() {
+        if vals[k].is_nan() {
+            if let Some(&near) = defined.iter().min_by_key(|&&d| dist(k, d)) {
+                vals[k] = vals[near];
+            }
+        }
+    }
+}
@@ -26,3 +26,296 @@ fn and_less_eq_or() {
     assert!(       x_and_y <= x_or_y       );
     assert!(                  x_or_y <= 1.0);
 }
+
+#[test]
+fn laws_hold() {
+    fn check<S: OpsetLaws>() {
+        let eq = |a: Membership<S>, b: Membership<S>| {
+            (a.as_raw() - b.as_raw()).abs() < 1e-9
+        };
+        let grid: Vec<Membership<S>> = (1..20)
+            .map(|i| Membership::new(i as f64 / 20.0))
+            .collect();
+        let zero = Membership::new(0.0);
+        let one = Membership::new(1.0);
+
+        for &a in &grid {
+            assert!(!S::IDEMPOTENT || eq(a & a, a) && eq(a | a, a));
+            assert!(!S::INVOLUTIVE || eq(!!a, a));
+            assert!(!S::BOUNDED || eq(a & one, a) && eq(a & zero, zero));
+            assert!(!S::BOUNDED || eq(a | zero, a) && eq(a | one, one));
+
+            for &b in &grid {
+                assert!(!S::DE_MORGAN || eq(!(a & b), !a | !b));
+                assert!(!S::DE_MORGAN || eq(!(a | b), !a & !b));
+
+                for &c in &grid {
+                    assert!(
+                        !S::DISTRIBUTIVE || eq(a & (b | c), (a & b) | (a & c))
+                    );
+                    assert!(
+                        !S::DISTRIBUTIVE || eq(a | (b & c), (a | b) & (a | c))
+                    );
+                }
+            }
+        }
+    }
+
+    check::<Yager1>();
+    check::<YagerInf>();
+    check::<Hamacher0>();
+    check::<Hamacher1>();
+    check::<Hamacher2>();
+}
+
+#[test]
+fn slices_match_scalars() {
+    fn check<S: Opset>() {
+        let lhs: Vec<Membership<S>> = (0..=20)
+            .map(|i| Membership::new(i as f64 / 20.0))
+            .collect();
+        let rhs: Vec<Membership<S>> = lhs.iter().rev().copied().collect();
+
+        let mut not = lhs.clone();
+        S::not_slice(&mut not);
+        let mut and = lhs.clone();
+        S::and_slice(&mut and, &rhs);
+        let mut or = lhs.clone();
+        S::or_slice(&mut or, &rhs);
+
+        for i in 0..lhs.len() {
+            assert_eq!(not[i], S::not(lhs[i]));
+            assert!(and[i] == S::and(lhs[i], rhs[i]) || and[i].is_nan());
+            assert!(or[i] == S::or(lhs[i], rhs[i]) || or[i].is_nan());
+        }
+    }
+
+    check::<Yager1>();
+    check::<YagerInf>();
+    check::<Hamacher0>();
+    check::<Hamacher1>();
+    check::<Hamacher2>();
+}
+
+#[test]
+fn lanes_match_scalars() {
+    use crate::value::LANES;
+
+    fn check<S: Opset>() {
+        let same = |a: Membership<S>, b: Membership<S>| {
+            a == b || (a.is_nan() && b.is_nan())
+        };
+        let grid: Vec<Membership<S>> = (0..=20)
+            .map(|i| Membership::new(i as f64 / 20.0))
+            .collect();
+        let pairs: Vec<_> = grid.iter()
+            .flat_map(|&a| grid.iter().map(move |&b| (a, b)))
+            .collect();
+
+        for chunk in pairs.chunks_exact(LANES) {
+            let lhs: [Membership<S>; LANES] =
+                std::array::from_fn(|i| chunk[i].0);
+            let rhs: [Membership<S>; LANES] =
+                std::array::from_fn(|i| chunk[i].1);
+
+            let not = S::not_x8(lhs);
+            let or = S::or_x8(lhs, rhs);
+            let and = S::and_x8(lhs, rhs);
+            for i in 0..LANES {
+                assert!(same(not[i], S::not(lhs[i])));
+                assert!(same(or[i], S::or(lhs[i], rhs[i])));
+                assert!(same(and[i], S::and(lhs[i], rhs[i])));
+            }
+        }
+    }
+
+    check::<Yager1>();
+    check::<YagerInf>();
+    check::<Hamacher0>();
+    check::<Hamacher1>();
+    check::<Hamacher2>();
+}
+
+#[test]
+fn lanes_fallback() {
+    // Opset written by hand, without lane-wise overrides.
+    #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+    struct Drastic;
+
+    impl Opset for Drastic {
+        fn not(val: Membership<Self>) -> Membership<Self> {
+            Membership::new(1.0 - val.as_raw())
+        }
+
+        fn or(lhs: Membership<Self>, rhs: Membership<Self>)
+            -> Membership<Self> {
+
+            match (lhs.as_raw(), rhs.as_raw()) {
+                (a, b) if a == 0.0 || b == 0.0 => Membership::new(a.max(b)),
+                _ => Membership::new(1.0)
+            }
+        }
+
+        fn and(lhs: Membership<Self>, rhs: Membership<Self>)
+            -> Membership<Self> {
+
+            match (lhs.as_raw(), rhs.as_raw()) {
+                (a, b) if a == 1.0 || b == 1.0 => Membership::new(a.min(b)),
+                _ => Membership::new(0.0)
+            }
+        }
+    }
+
+    let lhs = [0.0, 0.2, 1.0, 0.5, 0.3, 1.0, 0.0, 0.9, 0.4, 1.0]
+        .map(Membership::<Drastic>::new);
+    let rhs = [0.7, 1.0, 0.6, 0.5, 0.0, 1.0, 0.0, 0.1, 1.0, 0.3]
+        .map(Membership::<Drastic>::new);
+
+    let mut and = lhs;
+    Drastic::and_slice(&mut and, &rhs);
+    let mut or = lhs;
+    Drastic::or_slice(&mut or, &rhs);
+    let mut not = lhs;
+    Drastic::not_slice(&mut not);
+    for i in 0..lhs.len() {
+        assert_eq!(and[i], Drastic::and(lhs[i], rhs[i]));
+        assert_eq!(or[i], Drastic::or(lhs[i], rhs[i]));
+        assert_eq!(not[i], !lhs[i]);
+    }
+}
+
+#[test]
+fn scalar_formulas() {
+    // not declared `simd`: any formula, lanes evaluated value by value
+    fuzzy_opset! {
+        Nilpotent {
+             ~x   = 1.0 - x;
+            a | b = if a + b < 1.0 { a.max(b) } else { 1.0 };
+            a & b = if a + b > 1.0 { f64::min(a, b) } else { 0.0 };
+        }
+    }
+
+    let lhs = [0.0, 0.2, 1.0, 0.5, 0.3, 0.6, 0.0, 0.9]
+        .map(Membership::<Nilpotent>::new);
+    let rhs = [0.7, 1.0, 0.6, 0.5, 0.0, 0.8, 0.0, 0.1]
+        .map(Membership::<Nilpotent>::new);
+
+    let (and, or) = (Nilpotent::and_x8(lhs, rhs), Nilpotent::or_x8(lhs, rhs));
+    let not = Nilpotent::not_x8(lhs);
+    for i in 0..lhs.len() {
+        assert_eq!(and[i], lhs[i] & rhs[i]);
+        assert_eq!(or[i], lhs[i] | rhs[i]);
+        assert_eq!(not[i], !lhs[i]);
+    }
+    assert_eq!(and[5].as_raw(), 0.6);
+    assert_eq!(or[1].as_raw(), 1.0);
+}
+
+#[test]
+fn const_ops() {
+    fuzzy_opset! {
+        const Bounded {
+             ~x   = 1.0 - x;
+            a | b = (a + b).min(1.0);
+            a & b = (a + b - 1.0).max(0.0);
+        }
+    }
+
+    const N: usize = 11;
+    const GRID: [Membership<Hamacher2>; N] = {
+        let mut grid = [Membership::new(0.0); N];
+        let mut i = 0;
+        while i < N {
+            grid[i] = Membership::new(i as f64 / 10.0);
+            i += 1;
+        }
+        grid
+    };
+    const TABLE: [[Membership<Hamacher2>; N]; N] = {
+        let mut table = [[Membership::new(0.0); N]; N];
+        let mut i = 0;
+        while i < N {
+            let mut j = 0;
+            while j < N {
+                let and = Hamacher2::and(GRID[j], Hamacher2::not(GRID[j]));
+                table[i][j] = Hamacher2::or(GRID[i], and);
+                j += 1;
+            }
+            i += 1;
+        }
+        table
+    };
+
+    for i in 0..N {
+        for j in 0..N {
+            let (a, b) = (GRID[i], GRID[j]);
+            let expected = <Hamacher2 as Opset>::or(a, b & !b);
+            assert!(TABLE[i][j] == expected || expected.is_nan());
+        }
+    }
+
+    const A: Membership<Bounded> = Membership::new(0.7);
+    const B: Membership<Bounded> = Bounded::and(A, Bounded::not(A));
+    assert_eq!(B, A & !A);
+    assert_eq!(Bounded::or(A, A), A | A);
+}
+
+#[test]
+fn tabulated() {
+    type Fast = Tabulated<Hamacher1, 33>;
+
+    // exact at nodes, bilinear formulas (up to rounding) anywhere
+    let node = |i: usize| i as f64 / 32.0;
+    for i in 0..=32 {
+        let a = Membership::<Fast>::new(node(i));
+        let exact = Membership::<Hamacher1>::new(node(i));
+        assert_eq!((!a).as_raw(), (!exact).as_raw());
+    }
+    assert!(Fast::error_bound(96).max() < 1e-12);
+
+    // piecewise linear formulas are not
+    let bound = Tabulated::<Yager1, 33>::error_bound(128);
+    assert_eq!(bound.not(), 0.0);
+    assert!(bound.or() > 0.0 && bound.or() <= 1.0 / 64.0);
+    assert!(bound.and() > 0.0 && bound.and() <= 1.0 / 64.0);
+    assert_eq!(bound.max(), bound.or().max(bound.and()));
+
+    // undefined nodes take limits, not poisoning adjacent cells
+    type Hamacher0Fast = Tabulated<Hamacher0, 65>;
+    let bound = Hamacher0Fast::error_bound(256);
+    assert!(bound.max().is_finite() && bound.max() < 2e-3);
+    let within = |approx: Membership<Hamacher0Fast>, exact: Membership<_>| {
+        (approx.as_raw() - exact.as_raw()).abs() <= bound.max()
+    };
+    let (fast, exact) = (Hamacher0Fast::member, Hamacher0::member);
+    assert!((exact(0.0) & exact(0.0)).is_nan());
+    assert!(within(fast(0.004) & fast(0.004), exact(0.004) & exact(0.004)));
+    assert!(within(fast(0.999) | fast(0.999), exact(0.999) | exact(0.999)));
+    assert!((fast(0.0) & fast(0.0)).as_raw() < 1e-6);
+    assert!((fast(1.0) | fast(1.0)).as_raw() > 1.0 - 1e-6);
+
+    // tables of alternating opsets are told apart
+    type Coarse = Tabulated<Hamacher1, 9>;
+    let (x, y) = (0.3, 0.55);
+    for _ in 0..3 {
+        let fine = Fast::member(x) & Fast::member(y);
+        let coarse = Coarse::member(x) & Coarse::member(y);
+        assert!((fine.as_raw() - 0.3 * 0.55).abs() < 1e-12);
+        assert!((coarse.as_raw() - 0.3 * 0.55).abs() < 1e-12);
+        assert_eq!((!Coarse::member(x)).as_raw(), 1.0 - x);
+        assert_eq!((!Fast::member(y)).as_raw(), 1.0 - y);
+    }
+
+    // shared by threads
+    let vals: Vec<_> = (0..4)
+        .map(|i| std::thread::spawn(move || {
+            let a = Membership::<Tabulated<Hamacher1, 9>>::new(0.1 * i as f64);
+            (a | !a).as_raw()
+        }))
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    for (i, val) in vals.into_iter().enumerate() {
+        let a = Membership::<Hamacher1>::new(0.1 * i as f64);
+        assert!((val - (a | !a).as_raw()).abs() < 1e-9);
+    }
+}
@@ -1,6 +1,9 @@
+use std::array;
+use std::borrow::Borrow;
+use std::convert::TryInto;
 use std::fmt::Debug;
 
-use crate::value::{Membership, Raw};
+use crate::value::{Membership, MembershipX8, Raw, LANES};
 
 
 /// Fuzzy operations set.
@@ -37,6 +40,195 @@ pub trait Opset: Sized + Clone + Copy + Ord + Eq + Debug {
     /// ```
     fn and(lhs: Membership<Self>, rhs: Membership<Self>) -> Membership<Self>;
 
+    /// Fuzzy conjunction of all values, `1` if there are none.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Opset, Hamacher1};
+    /// let vals = [0.8, 0.5, 0.5].iter().map(|&raw| Hamacher1::member(raw));
+    /// let a = Hamacher1::and_all(vals);
+    /// assert!((a.as_raw() - 0.2).abs() < 0.001);
+    ///
+    /// let vals = [Hamacher1::member(0.8), Hamacher1::member(0.3)];
+    /// assert_eq!(Hamacher1::and_all(&vals), vals[0] & vals[1]);
+    /// assert_eq!(Hamacher1::and_all(&[]).as_raw(), 1.0);
+    /// ```
+    fn and_all<I>(vals: I) -> Membership<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Membership<Self>>
+    {
+        vals.into_iter()
+            .map(|val| *val.borrow())
+            .reduce(Self::and)
+            .unwrap_or_else(|| Membership::new(1.0))
+    }
+
+    /// Fuzzy alternative of all values, `0` if there are none.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Membership, Opset, Hamacher1};
+    /// let vals = vec![Hamacher1::member(0.8), Hamacher1::member(0.5)];
+    /// let a = Hamacher1::or_all(&vals);
+    /// assert!((a.as_raw() - 0.9).abs() < 0.001);
+    ///
+    /// let none: Vec<Membership<Hamacher1>> = vec![];
+    /// assert_eq!(Hamacher1::or_all(none).as_raw(), 0.0);
+    /// ```
+    fn or_all<I>(vals: I) -> Membership<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Membership<Self>>
+    {
+        vals.into_iter()
+            .map(|val| *val.borrow())
+            .reduce(Self::or)
+            .unwrap_or_else(|| Membership::new(0.0))
+    }
+
+    /// Fuzzy conjunction of `(value, weight)` pairs, each value weighted
+    /// as `max(1 - weight, value)`, `1` if there are none.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Opset, Hamacher1};
+    /// let vals = [
+    ///     (Hamacher1::member(0.2), Hamacher1::member(0.5)),
+    ///     (Hamacher1::member(0.9), Hamacher1::member(1.0))
+    /// ];
+    /// let a = Hamacher1::and_weighted(&vals);
+    /// assert!((a.as_raw() - 0.45).abs() < 0.001);
+    /// ```
+    fn and_weighted<I>(vals: I) -> Membership<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(Membership<Self>, Membership<Self>)>
+    {
+        Self::and_all(vals.into_iter().map(|pair| {
+            let (val, weight) = *pair.borrow();
+            Membership::new(val.as_raw().max(1.0 - weight.as_raw()))
+        }))
+    }
+
+    /// Fuzzy alternative of `(value, weight)` pairs, each value weighted
+    /// as `min(weight, value)`, `0` if there are none.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Opset, Hamacher1};
+    /// let vals = [
+    ///     (Hamacher1::member(0.9), Hamacher1::member(0.5)),
+    ///     (Hamacher1::member(0.2), Hamacher1::member(1.0))
+    /// ];
+    /// let a = Hamacher1::or_weighted(&vals);
+    /// assert!((a.as_raw() - 0.6).abs() < 0.001);
+    /// ```
+    fn or_weighted<I>(vals: I) -> Membership<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(Membership<Self>, Membership<Self>)>
+    {
+        Self::or_all(vals.into_iter().map(|pair| {
+            let (val, weight) = *pair.borrow();
+            Membership::new(val.as_raw().min(weight.as_raw()))
+        }))
+    }
+
+    /// Fuzzy negation of 8 values at once.
+    ///
+    /// Explicitly vectorised for opsets declared `simd` in `fuzzy_opset!`,
+    /// value by value otherwise.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Opset, Hamacher1};
+    /// let vals = [0.0, 0.25, 0.5, 0.75, 1.0, 0.1, 0.2, 0.3];
+    /// let nots = Hamacher1::not_x8(vals.map(Hamacher1::member));
+    /// assert_eq!(nots, vals.map(|val| !Hamacher1::member(val)));
+    /// ```
+    #[inline]
+    fn not_x8(vals: MembershipX8<Self>) -> MembershipX8<Self> {
+        vals.map(Self::not)
+    }
+
+    /// Fuzzy alternative of 8 pairs of values at once.
+    ///
+    /// Explicitly vectorised for opsets declared `simd` in `fuzzy_opset!`,
+    /// value by value otherwise.
+    #[inline]
+    fn or_x8(lhs: MembershipX8<Self>, rhs: MembershipX8<Self>)
+        -> MembershipX8<Self> {
+
+        array::from_fn(|i| Self::or(lhs[i], rhs[i]))
+    }
+
+    /// Fuzzy conjunction of 8 pairs of values at once.
+    ///
+    /// Explicitly vectorised for opsets declared `simd` in `fuzzy_opset!`,
+    /// value by value otherwise.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Opset, YagerInf};
+    /// let lhs = [0.2, 0.5, 0.9, 0.0, 1.0, 0.3, 0.3, 0.7];
+    /// let rhs = [0.4, 0.5, 0.1, 1.0, 1.0, 0.6, 0.2, 0.7];
+    /// let ands = YagerInf::and_x8(
+    ///     lhs.map(YagerInf::member),
+    ///     rhs.map(YagerInf::member)
+    /// );
+    /// assert_eq!(ands[0].as_raw(), 0.2);
+    /// assert_eq!(ands[2].as_raw(), 0.1);
+    /// ```
+    #[inline]
+    fn and_x8(lhs: MembershipX8<Self>, rhs: MembershipX8<Self>)
+        -> MembershipX8<Self> {
+
+        array::from_fn(|i| Self::and(lhs[i], rhs[i]))
+    }
+
+    /// Fuzzy negation of each value, in place.
+    ///
+    /// Runs 8 values at once via `not_x8`.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Opset, Hamacher1};
+    /// let mut vals = [0.0, 0.25, 1.0].map(Hamacher1::member);
+    /// Hamacher1::not_slice(&mut vals);
+    /// assert_eq!(vals, [1.0, 0.75, 0.0].map(Hamacher1::member));
+    /// ```
+    #[inline]
+    fn not_slice(vals: &mut [Membership<Self>]) {
+        let mut chunks = vals.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            let lanes = chunk.try_into().unwrap();
+            chunk.copy_from_slice(&Self::not_x8(lanes));
+        }
+        for val in chunks.into_remainder() {
+            *val = Self::not(*val);
+        }
+    }
+
+    /// Fuzzy alternative of each pair of values, in place of `lhs`.
+    /// Panics if the lengths differ.
+    ///
+    /// Runs 8 pairs at once via `or_x8`.
+    #[inline]
+    fn or_slice(lhs: &mut [Membership<Self>], rhs: &[Membership<Self>]) {
+        zip_slices(lhs, rhs, Self::or_x8, Self::or);
+    }
+
+    /// Fuzzy conjunction of each pair of values, in place of `lhs`.
+    /// Panics if the lengths differ.
+    ///
+    /// Runs 8 pairs at once via `and_x8`.
+    ///
+    /// ```rust
+    /// # use fuzzy_systems::{Opset, YagerInf};
+    /// let mut lhs = [0.2, 0.5, 0.9].map(YagerInf::member);
+    /// let rhs = [0.4, 0.5, 0.1].map(YagerInf::member);
+    /// YagerInf::and_slice(&mut lhs, &rhs);
+    /// assert_eq!(lhs, [0.2, 0.5, 0.1].map(YagerInf::member));
+    /// ```
+    #[inline]
+    fn and_slice(lhs: &mut [Membership<Self>], rhs: &[Membership<Self>]) {
+        zip_slices(lhs, rhs, Self::and_x8, Self::and);
+    }
+
     /// Creater member.
     ///
     /// ```rust
@@ -51,6 +243,53 @@ pub trait Opset: Sized + Clone + Copy + Ord + Eq + Debug {
 }
 
 
+/// Combines `rhs` into `lhs` pairwise, 8 pairs at once.
+#[inline]
+fn zip_slices<S: Opset>(
+    lhs: &mut [Membership<S>],
+    rhs: &[Membership<S>],
+    lanes: fn(MembershipX8<S>, MembershipX8<S>) -> MembershipX8<S>,
+    single: fn(Membership<S>, Membership<S>) -> Membership<S>
+) {
+    assert_eq!(lhs.len(), rhs.len());
+    let mut lhs_chunks = lhs.chunks_exact_mut(LANES);
+    let mut rhs_chunks = rhs.chunks_exact(LANES);
+    for (l, r) in (&mut lhs_chunks).zip(&mut rhs_chunks) {
+        let out = lanes(l.try_into().unwrap(), r.try_into().unwrap());
+        l.copy_from_slice(&out);
+    }
+    let rest = lhs_chunks.into_remainder();
+    for (l, &r) in rest.iter_mut().zip(rhs_chunks.remainder()) {
+        *l = single(*l, r);
+    }
+}
+
+
 /// Fuzzy operations set all operations of which are differentiable.
 pub trait OpsetDifferentiable: Opset {}
 
+
+
+/// Algebraic laws satisfied by fuzzy operations set.
+///
+/// Used to decide which rewrites preserve values, e.g. by
+/// `ExprDyn::simplify`
+/// or `ExprDyn::dnf`. Laws hold up to floating point rounding.
+pub trait OpsetLaws: Opset {
+    /// Idempotence: `a & a = a` and `a | a = a`.
+    const IDEMPOTENT: bool;
+
+    /// Involution of negation: `!!a = a`.
+    const INVOLUTIVE: bool;
+
+    /// De Morgan's laws: `!(a & b) = !a | !b` and `!(a | b) = !a & !b`.
+    const DE_MORGAN: bool;
+
+    /// Distributivity: `a & (b | c) = (a & b) | (a & c)`
+    /// and `a | (b & c) = (a | b) & (a | c)`.
+    const DISTRIBUTIVE: bool;
+
+    /// Boundary conditions: `a & 1 = a`, `a & 0 = 0`, `a | 0 = a`
+    /// and `a | 1 = 1`.
+    const BOUNDED: bool;
+}
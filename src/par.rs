@@ -0,0 +1,89 @@
+//! Parallel evaluation of large input collections, via `rayon`.
+//!
+//! Available with the `rayon` feature. Any indexed parallel iterator over
+//! borrowed inputs can be turned into one over their memberships, which
+//! keeps the inputs' order when collected:
+//!
+//! ```
+//! use rayon::prelude::*;
+//! use fuzzy_systems::{Expr, Hamacher1, Membership, Opset};
+//! use fuzzy_systems::par::ParallelInputs;
+//!
+//! let rule = Expr::<Hamacher1>::var(0) & !Expr::var(1);
+//! let member = |k: usize| Hamacher1::member(k as f64 / 100.0);
+//! let inputs: Vec<[Membership<Hamacher1>; 2]> = (0..10_000)
+//!     .map(|i| [member(i % 100), member(i % 7)])
+//!     .collect();
+//!
+//! let scores: Vec<_> = inputs.par_iter().eval_each(&rule).collect();
+//! assert_eq!(scores.len(), inputs.len());
+//! assert_eq!(scores[1234], inputs[1234][0] & !inputs[1234][1]);
+//! ```
+//!
+//! Crisp inputs are fuzzified alike:
+//!
+//! ```
+//! use rayon::prelude::*;
+//! use fuzzy_systems::{Hamacher1, Membership, Opset, Value};
+//! use fuzzy_systems::par::ParallelInputs;
+//!
+//! #[derive(PartialEq, PartialOrd)]
+//! struct Load(u32);
+//!
+//! impl<S: Opset> Value<S> for Load {
+//!     fn membership(&self) -> Membership<S> {
+//!         Membership::new(self.0.min(100) as f64 / 100.0)
+//!     }
+//! }
+//!
+//! let loads: Vec<_> = (0..10_000).map(|i| Load(i % 150)).collect();
+//! let levels: Vec<Membership<Hamacher1>> =
+//!     loads.par_iter().fuzzify().collect();
+//! assert_eq!(levels[42].as_raw(), 0.42);
+//! assert_eq!(levels[120].as_raw(), 1.0);
+//! ```
+
+#[cfg(test)]
+mod test;
+
+use rayon::iter::IndexedParallelIterator;
+
+use crate::expr::ExprWith;
+use crate::opset::Opset;
+use crate::value::{Membership, Value};
+
+
+/// Parallel iterator over borrowed inputs of fuzzy evaluation.
+///
+/// Implemented by all indexed parallel iterators over `&T`.
+pub trait ParallelInputs<'a, T>: IndexedParallelIterator<Item = &'a T>
+where
+    T: Sync + ?Sized + 'a
+{
+    /// Evaluates the expression with each input as its context.
+    fn eval_each<'e, S, E>(self, expr: &'e E)
+        -> impl IndexedParallelIterator<Item = Membership<S>> + 'e
+    where
+        Self: 'e,
+        'a: 'e,
+        S: Opset + Send,
+        E: ExprWith<S, T> + Sync
+    {
+        self.map(move |input| expr.eval_with(input))
+    }
+
+    /// Fuzzifies each input.
+    fn fuzzify<S>(self) -> impl IndexedParallelIterator<Item = Membership<S>>
+    where
+        S: Opset + Send,
+        T: Value<S>
+    {
+        self.map(Value::membership)
+    }
+}
+
+impl<'a, T, I> ParallelInputs<'a, T> for I
+where
+    T: Sync + ?Sized + 'a,
+    I: IndexedParallelIterator<Item = &'a T>
+{}
@@ -0,0 +1,77 @@
+// `Expr::var` is the documented shorthand and names the bare trait.
+#![allow(bare_trait_objects)]
+
+use rayon::prelude::*;
+
+use super::*;
+
+use crate::expr::Expr;
+use crate::opset::{Hamacher1, Opset};
+
+
+#[derive(PartialEq, PartialOrd, Debug)]
+struct Reading {
+    sensor: usize,
+    load: f64
+}
+
+impl<S: Opset> Value<S> for Reading {
+    fn membership(&self) -> Membership<S> {
+        Membership::new((self.load / 100.0).clamp(0.0, 1.0))
+    }
+}
+
+fn readings() -> Vec<Reading> {
+    (0..10_000)
+        .map(|i| Reading {
+            sensor: i,
+            load: (i * 37 % 150) as f64
+        })
+        .collect()
+}
+
+
+#[test]
+fn fuzzify_keeps_order() {
+    let inputs = readings();
+    let levels: Vec<Membership<Hamacher1>> = inputs
+        .par_iter()
+        .with_max_len(16)
+        .fuzzify()
+        .collect();
+
+    assert_eq!(levels.len(), inputs.len());
+    for (input, level) in inputs.iter().zip(&levels) {
+        assert_eq!(*level, input.membership(), "sensor {}", input.sensor);
+    }
+    assert_eq!(levels[1].as_raw(), 0.37);
+    assert_eq!(levels[4].as_raw(), 1.0);
+}
+
+#[test]
+fn eval_each_vars() {
+    let inputs: Vec<[Reading; 2]> = readings()
+        .into_iter()
+        .map(|a| {
+            let b = Reading {
+                sensor: a.sensor,
+                load: (a.sensor % 101) as f64
+            };
+            [a, b]
+        })
+        .collect();
+
+    let rule = Expr::<Hamacher1>::var(0) & !Expr::var(1);
+    let scores: Vec<_> = inputs
+        .par_iter()
+        .with_max_len(16)
+        .eval_each(&rule)
+        .collect();
+
+    assert_eq!(scores.len(), inputs.len());
+    for (input, score) in inputs.iter().zip(&scores) {
+        assert_eq!(*score, rule.eval_with(input));
+        let [a, b] = input;
+        assert_eq!(*score, a.membership() & !b.membership());
+    }
+}
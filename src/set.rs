@@ -0,0 +1,29 @@
+//! Fuzzy sets, assigning memberships to elements of a universe.
+//!
+//! Set operations (union, intersection, complement) use the opset of the
+//! memberships, so set-level logic agrees with expressions built on the
+//! same opset.
+//!
+//! Sets over finite universes are `DiscreteFuzzySet`s. Sets over real
+//! numbers are `ContinuousFuzzySet`s, given by membership functions,
+//! and can be sampled into `SampledFuzzySet`s.
+//!
+//! Any of them can be decomposed into crisp α-cuts, as sets of elements
+//! or lists of intervals, and rebuilt from them (`LevelSets`).
+//!
+//! `FuzzyNumber`s are fuzzy sets of real numbers with arithmetic,
+//! done on their α-cuts.
+
+mod discrete;
+mod continuous;
+mod sampled;
+mod level;
+mod number;
+#[cfg(test)]
+mod test;
+
+pub use self::discrete::{DiscreteFuzzySet, Iter};
+pub use self::continuous::ContinuousFuzzySet;
+pub use self::sampled::SampledFuzzySet;
+pub use self::level::LevelSets;
+pub use self::number::{FuzzyNumber, LrShape};
@@ -0,0 +1,255 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::ops::{BitAnd, BitOr, Not, RangeInclusive};
+use std::rc::Rc;
+
+use super::{LevelSets, SampledFuzzySet};
+
+use crate::expr::{Hedge, Modifier};
+use crate::opset::Opset;
+use crate::value::{Membership, Raw};
+
+
+/// Fuzzy set over real numbers, given by a membership function.
+///
+/// Set operations and hedges compose membership functions lazily,
+/// each evaluation at a point evaluates the whole composition.
+/// Estimations (discretisation, support, core, α-cuts) sample the set
+/// on its universe, an interval covering the interesting part of it:
+///
+/// ```
+/// # use fuzzy_systems::{ContinuousFuzzySet, Hedge, YagerInf};
+/// type Set = ContinuousFuzzySet<YagerInf>;
+/// let cold = Set::trapezoidal(-10.0, -10.0, 5.0, 15.0);
+/// let warm = Set::triangular(10.0, 20.0, 30.0);
+///
+/// let comfy = cold.clone() | warm.clone().hedge(Hedge::Very);
+/// assert_eq!(comfy.universe(), (-10.0, 30.0));
+/// assert_eq!(comfy.membership(12.5).as_raw(), 0.25);
+/// assert_eq!(comfy.membership(25.0).as_raw(), 0.25);
+///
+/// let core = comfy.core(401);
+/// assert_eq!(core, [-10.0..=5.0, 20.0..=20.0]);
+///
+/// let not_cold = !cold;
+/// assert_eq!(not_cold.membership(10.0).as_raw(), 0.5);
+/// assert_eq!(not_cold.membership(-20.0).as_raw(), 0.0);
+/// ```
+pub struct ContinuousFuzzySet<S: Opset> {
+    universe: (Raw, Raw),
+    f: Rc<dyn Fn(Raw) -> Membership<S>>
+}
+
+impl<S: Opset + 'static> ContinuousFuzzySet<S> {
+    /// Creates set with the membership function over the universe.
+    pub fn new<F>(universe: RangeInclusive<Raw>, f: F) -> Self
+    where
+        F: Fn(Raw) -> Membership<S> + 'static
+    {
+        let (lo, hi) = universe.into_inner();
+        assert!(lo <= hi);
+        Self {
+            universe: (lo, hi),
+            f: Rc::new(f)
+        }
+    }
+
+    /// Triangular set, rising from `a` to `b` and falling to `c`,
+    /// over universe `a..=c`.
+    pub fn triangular(a: Raw, b: Raw, c: Raw) -> Self {
+        Self::trapezoidal(a, b, b, c)
+    }
+
+    /// Trapezoidal set, rising from `a` to `b`, full up to `c`
+    /// and falling to `d`, over universe `a..=d`.
+    ///
+    /// `a = b` or `c = d` give shoulders, i.e. full membership
+    /// at the universe's edge and beyond it. `NaN` is not a member.
+    pub fn trapezoidal(a: Raw, b: Raw, c: Raw, d: Raw) -> Self {
+        assert!(a <= b && b <= c && c <= d);
+        Self::new(a..=d, move |x| {
+            let raw = if x.is_nan() {
+                0.0
+            } else if x < b {
+                if a == b { 1.0 }
+                else if x <= a { 0.0 }
+                else { (x - a) / (b - a) }
+            } else if x > c {
+                if c == d { 1.0 }
+                else if x >= d { 0.0 }
+                else { (d - x) / (d - c) }
+            } else {
+                1.0
+            };
+            Membership::new(raw)
+        })
+    }
+
+    /// Gaussian set centered at `mean`, over universe of 4 `sigma`s
+    /// to each side.
+    pub fn gaussian(mean: Raw, sigma: Raw) -> Self {
+        assert!(sigma > 0.0);
+        Self::new(mean - 4.0 * sigma..=mean + 4.0 * sigma, move |x| {
+            let z = (x - mean) / sigma;
+            Membership::new((-0.5 * z * z).exp())
+        })
+    }
+
+    /// Same set, over another universe.
+    pub fn with_universe(self, universe: RangeInclusive<Raw>) -> Self {
+        let (lo, hi) = universe.into_inner();
+        assert!(lo <= hi);
+        Self {
+            universe: (lo, hi),
+            f: self.f
+        }
+    }
+
+    /// Bounds of the universe.
+    pub fn universe(&self) -> (Raw, Raw) {
+        self.universe
+    }
+
+    /// Membership of the point.
+    #[inline]
+    pub fn membership(&self, x: Raw) -> Membership<S> {
+        (self.f)(x)
+    }
+
+    /// Union, via the opset's `or`, over both universes.
+    pub fn union(self, other: Self) -> Self {
+        self.zip(other, S::or)
+    }
+
+    /// Intersection, via the opset's `and`, over both universes.
+    pub fn intersection(self, other: Self) -> Self {
+        self.zip(other, S::and)
+    }
+
+    /// Complement, via the opset's `not`.
+    pub fn complement(self) -> Self {
+        self.map(S::not)
+    }
+
+    /// Set modified by a linguistic hedge.
+    pub fn hedge(self, hedge: Hedge) -> Self {
+        self.modify(hedge)
+    }
+
+    /// Set with memberships changed by the modifier.
+    pub fn modify<M>(self, op: M) -> Self
+    where
+        M: Modifier<S> + 'static
+    {
+        self.map(move |val| op.apply(val))
+    }
+
+    /// Memberships at `points` evenly spaced points of the universe,
+    /// its bounds included.
+    pub fn sample(&self, points: usize) -> SampledFuzzySet<S> {
+        let (lo, hi) = self.universe;
+        SampledFuzzySet::from_fn(lo..=hi, points, |x| self.membership(x))
+    }
+
+    /// Intervals of nonzero membership, estimated on `points` samples.
+    pub fn support(&self, points: usize) -> Vec<RangeInclusive<Raw>> {
+        self.sample(points).support()
+    }
+
+    /// Intervals of full membership, estimated on `points` samples.
+    pub fn core(&self, points: usize) -> Vec<RangeInclusive<Raw>> {
+        self.sample(points).core()
+    }
+
+    /// Intervals of membership at least `alpha`, estimated on `points`
+    /// samples.
+    pub fn alpha_cut(&self, alpha: Raw, points: usize)
+        -> Vec<RangeInclusive<Raw>> {
+
+        self.sample(points).alpha_cut(alpha)
+    }
+
+    /// Intervals of membership above `alpha`, estimated on `points`
+    /// samples.
+    pub fn strong_alpha_cut(&self, alpha: Raw, points: usize)
+        -> Vec<RangeInclusive<Raw>> {
+
+        self.sample(points).strong_alpha_cut(alpha)
+    }
+
+    /// α-cuts at `levels` evenly spaced levels, estimated on `points`
+    /// samples (see `SampledFuzzySet::level_sets`).
+    pub fn level_sets(&self, levels: usize, points: usize)
+        -> LevelSets<S, Vec<RangeInclusive<Raw>>> {
+
+        self.sample(points).level_sets(levels)
+    }
+
+    /// Set with memberships changed by `op`.
+    fn map<F>(self, op: F) -> Self
+    where
+        F: Fn(Membership<S>) -> Membership<S> + 'static
+    {
+        let f = self.f;
+        Self {
+            universe: self.universe,
+            f: Rc::new(move |x| op(f(x)))
+        }
+    }
+
+    /// Set over both universes with memberships combined by `op`.
+    fn zip<F>(self, other: Self, op: F) -> Self
+    where
+        F: Fn(Membership<S>, Membership<S>) -> Membership<S> + 'static
+    {
+        let universe = (
+            self.universe.0.min(other.universe.0),
+            self.universe.1.max(other.universe.1)
+        );
+        let (lhs, rhs) = (self.f, other.f);
+        Self {
+            universe,
+            f: Rc::new(move |x| op(lhs(x), rhs(x)))
+        }
+    }
+}
+
+impl<S: Opset> Clone for ContinuousFuzzySet<S> {
+    fn clone(&self) -> Self {
+        Self {
+            universe: self.universe,
+            f: self.f.clone()
+        }
+    }
+}
+
+impl<S: Opset> Debug for ContinuousFuzzySet<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let (lo, hi) = self.universe;
+        write!(f, "ContinuousFuzzySet({:?}..={:?})", lo, hi)
+    }
+}
+
+impl<S: Opset + 'static> Not for ContinuousFuzzySet<S> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl<S: Opset + 'static> BitOr for ContinuousFuzzySet<S> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl<S: Opset + 'static> BitAnd for ContinuousFuzzySet<S> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
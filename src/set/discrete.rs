@@ -0,0 +1,385 @@
+use std::borrow::Borrow;
+use std::collections::btree_map::{self, BTreeMap};
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Not};
+
+use super::LevelSets;
+
+use crate::opset::Opset;
+use crate::value::{Membership, Raw, Value};
+
+
+/// Fuzzy set over a finite universe.
+///
+/// Holds memberships of the elements of its universe, in order.
+/// Elements outside of the universe have membership `0`:
+///
+/// ```
+/// # use fuzzy_systems::{DiscreteFuzzySet, Hamacher1, Membership};
+/// let price = |name: &&str| match *name {
+///     "bread" => 1.5,
+///     "cheese" => 6.0,
+///     "wine" => 12.0,
+///     _ => unreachable!()
+/// };
+/// let products = vec!["bread", "cheese", "wine"];
+///
+/// let cheap = DiscreteFuzzySet::<_, Hamacher1>::from_fn(products, |p| {
+///     Membership::with_fit((10.0 - price(p)) / 8.0)
+/// });
+/// assert_eq!(cheap.to_string(), "{1/bread, 0.5/cheese, 0/wine}");
+/// assert_eq!(cheap.support().collect::<Vec<_>>(), [&"bread", &"cheese"]);
+/// assert_eq!(cheap.membership("caviar").as_raw(), 0.0);
+///
+/// let pricey = !&cheap;
+/// let either = &cheap | &pricey;
+/// assert_eq!(either.membership("cheese").as_raw(), 0.75);
+/// ```
+pub struct DiscreteFuzzySet<T: Ord, S: Opset> {
+    members: BTreeMap<T, Membership<S>>
+}
+
+impl<T: Ord, S: Opset> DiscreteFuzzySet<T, S> {
+    /// Creates set with empty universe.
+    pub fn new() -> Self {
+        Self {
+            members: BTreeMap::new()
+        }
+    }
+
+    /// Creates set over the universe with memberships given by `f`.
+    pub fn from_fn<I, F>(universe: I, mut f: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(&T) -> Membership<S>
+    {
+        universe.into_iter()
+            .map(|elem| {
+                let val = f(&elem);
+                (elem, val)
+            })
+            .collect()
+    }
+
+    /// Creates set over the universe with the elements' own memberships.
+    pub fn from_values<I>(universe: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Value<S>
+    {
+        Self::from_fn(universe, Value::membership)
+    }
+
+    /// Adds element to the universe, or changes its membership.
+    /// Returns the previous membership, if any.
+    pub fn insert(&mut self, elem: T, val: Membership<S>)
+        -> Option<Membership<S>> {
+
+        self.members.insert(elem, val)
+    }
+
+    /// Removes element from the universe.
+    /// Returns its membership, if any.
+    pub fn remove<Q>(&mut self, elem: &Q) -> Option<Membership<S>>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized
+    {
+        self.members.remove(elem)
+    }
+
+    /// Membership of the element, `0` if outside of the universe.
+    pub fn membership<Q>(&self, elem: &Q) -> Membership<S>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized
+    {
+        self.members.get(elem).copied().unwrap_or(Membership::new(0.0))
+    }
+
+    /// Whether the element is in the universe.
+    pub fn contains<Q>(&self, elem: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized
+    {
+        self.members.contains_key(elem)
+    }
+
+    /// Size of the universe.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the universe is empty.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Elements of the universe with their memberships, in order.
+    pub fn iter(&self) -> Iter<'_, T, S> {
+        Iter {
+            inner: self.members.iter()
+        }
+    }
+
+    /// Elements of nonzero membership.
+    pub fn support(&self) -> impl Iterator<Item = &T> + '_ {
+        self.strong_alpha_cut(0.0)
+    }
+
+    /// Elements of full membership.
+    pub fn core(&self) -> impl Iterator<Item = &T> + '_ {
+        self.alpha_cut(1.0)
+    }
+
+    /// Elements of membership at least `alpha` (α-cut).
+    pub fn alpha_cut(&self, alpha: Raw) -> impl Iterator<Item = &T> + '_ {
+        self.iter()
+            .filter(move |(_, val)| val.as_raw() >= alpha)
+            .map(|(elem, _)| elem)
+    }
+
+    /// Elements of membership above `alpha` (strong α-cut).
+    pub fn strong_alpha_cut(&self, alpha: Raw)
+        -> impl Iterator<Item = &T> + '_ {
+
+        self.iter()
+            .filter(move |(_, val)| val.as_raw() > alpha)
+            .map(|(elem, _)| elem)
+    }
+
+    /// Largest membership, `0` if the universe is empty.
+    pub fn height(&self) -> Membership<S> {
+        self.members.values()
+            .copied()
+            .fold(Membership::new(0.0), |max, val| {
+                if val > max { val } else { max }
+            })
+    }
+
+    /// Whether some element has full membership.
+    pub fn is_normal(&self) -> bool {
+        self.height().as_raw() == 1.0
+    }
+}
+
+impl<T: Ord + Clone, S: Opset> DiscreteFuzzySet<T, S> {
+    /// Set with memberships divided by the height, so it's normal.
+    /// `None` if all memberships are `0`.
+    pub fn normalize(&self) -> Option<Self> {
+        let height = self.height().as_raw();
+        if height == 0.0 {
+            return None;
+        }
+        Some(self.map(|val| Membership::with_fit(val.as_raw() / height)))
+    }
+
+    /// α-cuts at each membership level of the set, from which it can be
+    /// rebuilt exactly.
+    ///
+    /// ```
+    /// # use fuzzy_systems::{DiscreteFuzzySet, Membership, YagerInf};
+    /// let set: DiscreteFuzzySet<_, YagerInf> = vec![
+    ///     ('a', Membership::new(0.5)),
+    ///     ('b', Membership::new(1.0)),
+    ///     ('c', Membership::new(0.5))
+    /// ].into_iter().collect();
+    ///
+    /// let levels = set.level_sets();
+    /// let cuts: Vec<_> = levels.iter()
+    ///     .map(|(alpha, cut)| {
+    ///         (alpha.as_raw(), cut.iter().collect::<String>())
+    ///     })
+    ///     .collect();
+    /// assert_eq!(cuts, [(0.5, "abc".to_string()), (1.0, "b".to_string())]);
+    /// assert_eq!(levels.to_set(), set);
+    /// ```
+    pub fn level_sets(&self) -> LevelSets<S, BTreeSet<T>> {
+        let mut alphas: Vec<_> = self.members.values().copied().collect();
+        alphas.sort_by(|a, b| a.partial_cmp(b).expect("Invalid membership!"));
+        alphas.dedup();
+
+        let levels = alphas.into_iter()
+            .map(|alpha| {
+                let cut = self.alpha_cut(alpha.as_raw()).cloned().collect();
+                (alpha, cut)
+            })
+            .collect();
+        LevelSets::new(levels)
+    }
+
+    /// Union, memberships combined with the opset's `or`.
+    ///
+    /// The universe is the union of both universes.
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip(other, S::or)
+    }
+
+    /// Intersection, memberships combined with the opset's `and`.
+    ///
+    /// The universe is the union of both universes.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip(other, S::and)
+    }
+
+    /// Complement within the universe, via the opset's `not`.
+    pub fn complement(&self) -> Self {
+        self.map(S::not)
+    }
+
+    /// Set with each membership changed by `f`.
+    pub fn map<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(Membership<S>) -> Membership<S>
+    {
+        self.iter()
+            .map(|(elem, val)| (elem.clone(), f(val)))
+            .collect()
+    }
+
+    /// Set over both universes with memberships combined by `f`.
+    fn zip<F>(&self, other: &Self, f: F) -> Self
+    where
+        F: Fn(Membership<S>, Membership<S>) -> Membership<S>
+    {
+        let elems = self.members.keys().chain(other.members.keys());
+        let mut members = BTreeMap::new();
+        for elem in elems {
+            members.entry(elem.clone()).or_insert_with(|| {
+                f(self.membership(elem), other.membership(elem))
+            });
+        }
+        Self {
+            members
+        }
+    }
+}
+
+impl<T: Ord, S: Opset> Default for DiscreteFuzzySet<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone, S: Opset> Clone for DiscreteFuzzySet<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            members: self.members.clone()
+        }
+    }
+}
+
+impl<T: Ord, S: Opset> PartialEq for DiscreteFuzzySet<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.members == other.members
+    }
+}
+
+impl<T: Ord + Debug, S: Opset> Debug for DiscreteFuzzySet<T, S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "DiscreteFuzzySet({:?})", self.members)
+    }
+}
+
+/// Zadeh's notation: `{1/a, 0.5/b}`.
+impl<T: Ord + Display, S: Opset> Display for DiscreteFuzzySet<T, S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{{")?;
+        for (i, (elem, val)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}/{}", val, elem)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<T: Ord, S: Opset> FromIterator<(T, Membership<S>)>
+    for DiscreteFuzzySet<T, S>
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (T, Membership<S>)>
+    {
+        Self {
+            members: iter.into_iter().collect()
+        }
+    }
+}
+
+impl<T: Ord, S: Opset> Extend<(T, Membership<S>)> for DiscreteFuzzySet<T, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (T, Membership<S>)>
+    {
+        self.members.extend(iter);
+    }
+}
+
+impl<'a, T: Ord, S: Opset> IntoIterator for &'a DiscreteFuzzySet<T, S> {
+    type Item = (&'a T, Membership<S>);
+    type IntoIter = Iter<'a, T, S>;
+
+    fn into_iter(self) -> Iter<'a, T, S> {
+        self.iter()
+    }
+}
+
+impl<T: Ord, S: Opset> IntoIterator for DiscreteFuzzySet<T, S> {
+    type Item = (T, Membership<S>);
+    type IntoIter = btree_map::IntoIter<T, Membership<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.into_iter()
+    }
+}
+
+impl<T: Ord + Clone, S: Opset> Not for &DiscreteFuzzySet<T, S> {
+    type Output = DiscreteFuzzySet<T, S>;
+
+    fn not(self) -> DiscreteFuzzySet<T, S> {
+        self.complement()
+    }
+}
+
+impl<T: Ord + Clone, S: Opset> BitOr for &DiscreteFuzzySet<T, S> {
+    type Output = DiscreteFuzzySet<T, S>;
+
+    fn bitor(self, rhs: Self) -> DiscreteFuzzySet<T, S> {
+        self.union(rhs)
+    }
+}
+
+impl<T: Ord + Clone, S: Opset> BitAnd for &DiscreteFuzzySet<T, S> {
+    type Output = DiscreteFuzzySet<T, S>;
+
+    fn bitand(self, rhs: Self) -> DiscreteFuzzySet<T, S> {
+        self.intersection(rhs)
+    }
+}
+
+
+/// Iterator over elements of a `DiscreteFuzzySet` with their memberships.
+pub struct Iter<'a, T, S: Opset> {
+    inner: btree_map::Iter<'a, T, Membership<S>>
+}
+
+impl<'a, T, S: Opset> Iterator for Iter<'a, T, S> {
+    type Item = (&'a T, Membership<S>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(elem, val)| (elem, *val))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, S: Opset> ExactSizeIterator for Iter<'a, T, S> {}
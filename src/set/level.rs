@@ -0,0 +1,133 @@
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use super::{ContinuousFuzzySet, DiscreteFuzzySet};
+
+use crate::opset::Opset;
+use crate::value::{Membership, Raw};
+
+
+/// Fuzzy set decomposed into crisp α-cuts.
+///
+/// Each level holds membership `alpha` and the crisp set `C` of elements
+/// of membership at least `alpha`, either a `BTreeSet` of elements or
+/// a list of intervals. The fuzzy set is rebuilt via the resolution
+/// identity: an element's membership is the highest level whose cut
+/// contains it (`0` if none does).
+///
+/// ```
+/// # use fuzzy_systems::{ContinuousFuzzySet, LevelSets, Membership, YagerInf};
+/// let warm = ContinuousFuzzySet::<YagerInf>::triangular(10.0, 20.0, 30.0);
+/// let levels = warm.level_sets(4, 201);
+/// let (alpha, cut) = &levels.levels()[1];
+/// assert_eq!((alpha.as_raw(), cut.clone()), (0.5, vec![15.0..=25.0]));
+///
+/// // memberships rounded down to the levels
+/// let rebuilt = levels.to_set();
+/// assert_eq!(rebuilt.membership(17.0).as_raw(), 0.5);
+/// assert_eq!(rebuilt.membership(20.0).as_raw(), 1.0);
+///
+/// // cuts from interval-based tools
+/// let level = |alpha, lo, hi| (Membership::new(alpha), vec![lo..=hi]);
+/// let about_5 = LevelSets::<YagerInf, _>::new(vec![
+///     level(0.5, 4.0, 6.0),
+///     level(1.0, 5.0, 5.0)
+/// ]);
+/// assert_eq!(about_5.membership(&4.5).as_raw(), 0.5);
+/// ```
+pub struct LevelSets<S: Opset, C> {
+    levels: Vec<(Membership<S>, C)>
+}
+
+impl<S: Opset, C> LevelSets<S, C> {
+    /// Creates from levels with their α-cuts, in any order.
+    pub fn new(levels: Vec<(Membership<S>, C)>) -> Self {
+        Self {
+            levels
+        }
+    }
+
+    /// Levels with their α-cuts.
+    pub fn levels(&self) -> &[(Membership<S>, C)] {
+        &self.levels
+    }
+
+    /// Iterator over levels with their α-cuts.
+    pub fn iter(&self) -> impl Iterator<Item = (Membership<S>, &C)> + '_ {
+        self.levels.iter().map(|(alpha, cut)| (*alpha, cut))
+    }
+
+    /// Number of levels.
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Whether there are no levels.
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Highest level whose cut satisfies `contains`, `0` if none.
+    fn highest<F>(&self, mut contains: F) -> Membership<S>
+    where
+        F: FnMut(&C) -> bool
+    {
+        self.levels.iter()
+            .filter(|(_, cut)| contains(cut))
+            .map(|(alpha, _)| *alpha)
+            .fold(Membership::new(0.0), |max, alpha| {
+                if alpha > max { alpha } else { max }
+            })
+    }
+}
+
+impl<S: Opset, T: Ord + Clone> LevelSets<S, BTreeSet<T>> {
+    /// Membership of the element, by the resolution identity.
+    pub fn membership(&self, elem: &T) -> Membership<S> {
+        self.highest(|cut| cut.contains(elem))
+    }
+
+    /// Fuzzy set over all elements of the cuts, by the resolution
+    /// identity.
+    pub fn to_set(&self) -> DiscreteFuzzySet<T, S> {
+        let elems: BTreeSet<&T> = self.levels.iter()
+            .flat_map(|(_, cut)| cut)
+            .collect();
+        DiscreteFuzzySet::from_fn(elems.into_iter().cloned(), |elem| {
+            self.membership(elem)
+        })
+    }
+}
+
+impl<S: Opset + 'static> LevelSets<S, Vec<RangeInclusive<Raw>>> {
+    /// Membership of the point, by the resolution identity.
+    pub fn membership(&self, x: &Raw) -> Membership<S> {
+        self.highest(|cut| cut.iter().any(|range| range.contains(x)))
+    }
+
+    /// Fuzzy set over the hull of the cuts, by the resolution identity.
+    pub fn to_set(&self) -> ContinuousFuzzySet<S> {
+        let bounds = self.levels.iter()
+            .flat_map(|(_, cut)| cut)
+            .map(|range| (*range.start(), *range.end()))
+            .reduce(|(lo, hi), (start, end)| (lo.min(start), hi.max(end)));
+        let (lo, hi) = bounds.unwrap_or((0.0, 0.0));
+
+        let levels = Rc::new(self.clone());
+        ContinuousFuzzySet::new(lo..=hi, move |x| levels.membership(&x))
+    }
+}
+
+impl<S: Opset, C: Clone> Clone for LevelSets<S, C> {
+    fn clone(&self) -> Self {
+        Self::new(self.levels.clone())
+    }
+}
+
+impl<S: Opset, C: Debug> Debug for LevelSets<S, C> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "LevelSets({:?})", self.levels)
+    }
+}
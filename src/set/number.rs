@@ -0,0 +1,391 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, RangeInclusive, Sub};
+
+use super::{ContinuousFuzzySet, LevelSets, SampledFuzzySet};
+
+use crate::opset::Opset;
+use crate::value::{Membership, Raw};
+
+
+/// Shape of a side of an LR-type fuzzy number, see `FuzzyNumber::lr`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LrShape {
+    /// `1 - t`.
+    Linear,
+    /// `1 - t^p`, flatter near the core for `p > 1`.
+    Power(Raw)
+}
+
+impl LrShape {
+    /// Distance from the core, relative to the spread, where the shape
+    /// falls to `alpha`.
+    fn inverse(self, alpha: Raw) -> Raw {
+        match self {
+            LrShape::Linear => 1.0 - alpha,
+            LrShape::Power(exp) => (1.0 - alpha).powf(1.0 / exp)
+        }
+    }
+}
+
+
+/// Fuzzy number, i.e. a normal convex fuzzy set of real numbers,
+/// held as its α-cut intervals at `N` evenly spaced levels.
+///
+/// Levels are `0` (closure of the support), `1 / (N - 1)`, ..., `1`
+/// (the core). Arithmetic is done cut by cut with interval arithmetic,
+/// so it's exact at the levels; memberships are interpolated linearly
+/// between them:
+///
+/// ```
+/// # use fuzzy_systems::{FuzzyNumber, Hamacher1};
+/// type Number = FuzzyNumber<Hamacher1>;
+/// let about_5 = Number::triangular(4.0, 5.0, 6.0);
+/// let roughly_3 = Number::trapezoidal(2.0, 2.5, 3.5, 4.0);
+///
+/// let sum = about_5 + roughly_3;
+/// assert_eq!(sum.core(), 7.5..=8.5);
+/// assert_eq!(sum.support(), 6.0..=10.0);
+/// assert_eq!(sum.membership(6.75).as_raw(), 0.5);
+///
+/// let product = about_5 * roughly_3 * 2.0;
+/// assert_eq!(product.core(), 25.0..=35.0);
+/// assert_eq!(product.membership(8.0 * 2.0).as_raw(), 0.0);
+///
+/// assert_eq!(about_5.possibly_le(&roughly_3).as_raw(), 0.0);
+/// assert_eq!(roughly_3.necessarily_le(&about_5).as_raw(), 1.0);
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct FuzzyNumber<S: Opset, const N: usize = 21> {
+    cuts: [(Raw, Raw); N],
+    phantom: PhantomData<S>
+}
+
+impl<S: Opset, const N: usize> FuzzyNumber<S, N> {
+    /// Creates from α-cuts at levels `0`, `1 / (N - 1)`, ..., `1`.
+    /// Panics unless the cuts are nested intervals.
+    pub fn from_cuts(cuts: [(Raw, Raw); N]) -> Self {
+        const { assert!(N >= 2, "Fuzzy number needs at least 2 levels!") };
+        for (k, &(lo, hi)) in cuts.iter().enumerate() {
+            assert!(lo <= hi);
+            if let Some(&(next_lo, next_hi)) = cuts.get(k + 1) {
+                assert!(lo <= next_lo && next_hi <= hi);
+            }
+        }
+        Self {
+            cuts,
+            phantom: PhantomData
+        }
+    }
+
+    /// Crisp number, of membership `1` at `x` only.
+    pub fn crisp(x: Raw) -> Self {
+        Self::from_cuts([(x, x); N])
+    }
+
+    /// Triangular number, rising from `a` to `b` and falling to `c`.
+    pub fn triangular(a: Raw, b: Raw, c: Raw) -> Self {
+        Self::trapezoidal(a, b, b, c)
+    }
+
+    /// Trapezoidal number, rising from `a` to `b`, full up to `c`
+    /// and falling to `d`.
+    pub fn trapezoidal(a: Raw, b: Raw, c: Raw, d: Raw) -> Self {
+        assert!(a <= b && b <= c && c <= d);
+        Self::lr(b, c, b - a, d - c, LrShape::Linear, LrShape::Linear)
+    }
+
+    /// LR-type number with core `m..=n`, spreads `left` and `right`,
+    /// falling to the sides with shapes `l` and `r`.
+    pub fn lr(m: Raw, n: Raw, left: Raw, right: Raw, l: LrShape, r: LrShape)
+        -> Self {
+
+        assert!(m <= n && left >= 0.0 && right >= 0.0);
+        Self::from_cuts(std::array::from_fn(|k| {
+            let alpha = Self::level(k);
+            (m - left * l.inverse(alpha), n + right * r.inverse(alpha))
+        }))
+    }
+
+    /// α-cuts at levels `0`, `1 / (N - 1)`, ..., `1`.
+    pub fn cuts(&self) -> &[(Raw, Raw); N] {
+        &self.cuts
+    }
+
+    /// Closure of the support, the α-cut at `0`.
+    pub fn support(&self) -> RangeInclusive<Raw> {
+        let (lo, hi) = self.cuts[0];
+        lo..=hi
+    }
+
+    /// Core, the α-cut at `1`.
+    pub fn core(&self) -> RangeInclusive<Raw> {
+        let (lo, hi) = self.cuts[N - 1];
+        lo..=hi
+    }
+
+    /// α-cut, interpolated between the levels.
+    pub fn alpha_cut(&self, alpha: Raw) -> RangeInclusive<Raw> {
+        assert!((0.0..=1.0).contains(&alpha));
+        let t = alpha * (N - 1) as Raw;
+        let k = (t as usize).min(N - 2);
+        let ft = t - k as Raw;
+        let ((lo0, hi0), (lo1, hi1)) = (self.cuts[k], self.cuts[k + 1]);
+        lo0 * (1.0 - ft) + lo1 * ft..=hi0 * (1.0 - ft) + hi1 * ft
+    }
+
+    /// Membership of the crisp value, interpolated between the levels.
+    pub fn membership(&self, x: Raw) -> Membership<S> {
+        let contains = |k: usize| self.cuts[k].0 <= x && x <= self.cuts[k].1;
+        if !contains(0) {
+            return Membership::new(0.0);
+        }
+
+        // cuts are nested, so the highest one containing `x` is found
+        // by bisection
+        let (mut k, mut above) = (0, N);
+        while above - k > 1 {
+            let mid = (k + above) / 2;
+            if contains(mid) { k = mid } else { above = mid }
+        }
+        if k == N - 1 {
+            return Membership::new(1.0);
+        }
+
+        let ((lo0, hi0), (lo1, hi1)) = (self.cuts[k], self.cuts[k + 1]);
+        let t = if x < lo1 {
+            (x - lo0) / (lo1 - lo0)
+        } else {
+            (hi0 - x) / (hi0 - hi1)
+        };
+        Membership::with_fit(Self::level(k) + t / (N - 1) as Raw)
+    }
+
+    /// Mean of the α-cuts' midpoints, a crisp representative.
+    pub fn defuzzify(&self) -> Raw {
+        // trapezoidal rule over the levels
+        let mid = |k: usize| (self.cuts[k].0 + self.cuts[k].1) / 2.0;
+        let inner: Raw = (1..N - 1).map(mid).sum();
+        (inner + (mid(0) + mid(N - 1)) / 2.0) / (N - 1) as Raw
+    }
+
+    /// Degree of possibility that this number is at most `other`.
+    pub fn possibly_le(&self, other: &Self) -> Membership<S> {
+        self.highest(|k| other.cuts[k].1 - self.cuts[k].0)
+    }
+
+    /// Degree of possibility that this number is at least `other`.
+    pub fn possibly_ge(&self, other: &Self) -> Membership<S> {
+        other.possibly_le(self)
+    }
+
+    /// Degree of necessity that this number is at most `other`,
+    /// the opset's negation of the possibility of being greater.
+    pub fn necessarily_le(&self, other: &Self) -> Membership<S> {
+        S::not(self.highest(|k| self.cuts[k].1 - other.cuts[k].0))
+    }
+
+    /// Degree of necessity that this number is at least `other`.
+    pub fn necessarily_ge(&self, other: &Self) -> Membership<S> {
+        other.necessarily_le(self)
+    }
+
+    /// Quotient, `None` if a cut of the divisor contains `0`.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.cuts[0].0 <= 0.0 && 0.0 <= rhs.cuts[0].1 {
+            return None;
+        }
+        Some(self.zip(rhs, |(a, b), (c, d)| {
+            Self::hull([a / c, a / d, b / c, b / d])
+        }))
+    }
+
+    /// Number with each cut changed by `op`.
+    pub fn map<F>(self, op: F) -> Self
+    where
+        F: FnMut((Raw, Raw)) -> (Raw, Raw)
+    {
+        Self::from_cuts(self.cuts.map(op))
+    }
+
+    /// Result of `f` by the extension principle, with memberships
+    /// combined by the opset's `and` instead of `min`.
+    ///
+    /// Estimated on `points` samples of each support, binned into
+    /// `points` samples of the result. With `YagerInf` (`min`)
+    /// it agrees with α-cut arithmetic, other opsets give lower
+    /// memberships.
+    pub fn extend<F>(&self, other: &Self, f: F, points: usize)
+        -> SampledFuzzySet<S>
+    where
+        F: Fn(Raw, Raw) -> Raw
+    {
+        assert!(points >= 2);
+        let samples = |num: &Self| {
+            let (lo, hi) = num.cuts[0];
+            SampledFuzzySet::from_fn(lo..=hi, points, |x| num.membership(x))
+        };
+        let (lhs, rhs) = (samples(self), samples(other));
+        let f = &f;
+
+        let vals: Vec<_> = lhs.iter()
+            .flat_map(|(x, a)| {
+                rhs.iter().map(move |(y, b)| (f(x, y), S::and(a, b)))
+            })
+            .collect();
+        let (lo, hi) = vals.iter().fold(
+            (Raw::INFINITY, Raw::NEG_INFINITY),
+            |(lo, hi), &(z, _)| (lo.min(z), hi.max(z))
+        );
+
+        let mut bins = vec![Membership::new(0.0); points];
+        for (z, val) in vals {
+            let t = if hi > lo { (z - lo) / (hi - lo) } else { 0.0 };
+            let bin = &mut bins[(t * (points - 1) as Raw).round() as usize];
+            if val > *bin {
+                *bin = val;
+            }
+        }
+        SampledFuzzySet::new(lo..=hi, bins)
+    }
+
+    /// Fuzzy set of the number, over its support.
+    pub fn to_set(&self) -> ContinuousFuzzySet<S>
+    where
+        S: 'static
+    {
+        let num = *self;
+        ContinuousFuzzySet::new(self.support(), move |x| num.membership(x))
+    }
+
+    /// α-cuts at the levels, as level sets.
+    pub fn level_sets(&self) -> LevelSets<S, Vec<RangeInclusive<Raw>>> {
+        let levels = self.cuts.iter()
+            .enumerate()
+            .map(|(k, &(lo, hi))| {
+                (Membership::new(Self::level(k)), vec![lo..=hi])
+            })
+            .collect();
+        LevelSets::new(levels)
+    }
+
+    /// Highest level where `gap`, decreasing with the levels, is still
+    /// nonnegative, interpolated between the levels; `0` if none.
+    fn highest<F>(&self, gap: F) -> Membership<S>
+    where
+        F: Fn(usize) -> Raw
+    {
+        if gap(0) < 0.0 {
+            return Membership::new(0.0);
+        }
+        let k = (0..N).rev().find(|&k| gap(k) >= 0.0).unwrap_or(0);
+        if k == N - 1 {
+            return Membership::new(1.0);
+        }
+        let t = gap(k) / (gap(k) - gap(k + 1));
+        Membership::with_fit(Self::level(k) + t / (N - 1) as Raw)
+    }
+
+    /// Number with cuts of both numbers combined by `op`.
+    fn zip<F>(self, rhs: Self, mut op: F) -> Self
+    where
+        F: FnMut((Raw, Raw), (Raw, Raw)) -> (Raw, Raw)
+    {
+        Self::from_cuts(std::array::from_fn(|k| op(self.cuts[k], rhs.cuts[k])))
+    }
+
+    fn hull(vals: [Raw; 4]) -> (Raw, Raw) {
+        vals.iter().fold((Raw::INFINITY, Raw::NEG_INFINITY), |(lo, hi), &val| {
+            (lo.min(val), hi.max(val))
+        })
+    }
+
+    #[inline]
+    fn level(k: usize) -> Raw {
+        k as Raw / (N - 1) as Raw
+    }
+}
+
+impl<S: Opset, const N: usize> Add for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.zip(rhs, |(a, b), (c, d)| (a + c, b + d))
+    }
+}
+
+impl<S: Opset, const N: usize> Sub for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(rhs, |(a, b), (c, d)| (a - d, b - c))
+    }
+}
+
+impl<S: Opset, const N: usize> Mul for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.zip(rhs, |(a, b), (c, d)| {
+            Self::hull([a * c, a * d, b * c, b * d])
+        })
+    }
+}
+
+impl<S: Opset, const N: usize> Debug for FuzzyNumber<S, N> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "FuzzyNumber({:?})", self.cuts)
+    }
+}
+
+/// Panics if the divisor's support contains `0`, see `checked_div`.
+impl<S: Opset, const N: usize> Div for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        self.checked_div(rhs).expect("Division by fuzzy number around zero!")
+    }
+}
+
+impl<S: Opset, const N: usize> Neg for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|(lo, hi)| (-hi, -lo))
+    }
+}
+
+impl<S: Opset, const N: usize> Add<Raw> for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Raw) -> Self {
+        self.map(|(lo, hi)| (lo + rhs, hi + rhs))
+    }
+}
+
+impl<S: Opset, const N: usize> Sub<Raw> for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Raw) -> Self {
+        self.map(|(lo, hi)| (lo - rhs, hi - rhs))
+    }
+}
+
+impl<S: Opset, const N: usize> Mul<Raw> for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Raw) -> Self {
+        self.map(|(lo, hi)| {
+            if rhs < 0.0 { (hi * rhs, lo * rhs) } else { (lo * rhs, hi * rhs) }
+        })
+    }
+}
+
+impl<S: Opset, const N: usize> Div<Raw> for FuzzyNumber<S, N> {
+    type Output = Self;
+
+    fn div(self, rhs: Raw) -> Self {
+        assert!(rhs != 0.0, "Division by zero!");
+        self * (1.0 / rhs)
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::ops::RangeInclusive;
+
+use super::LevelSets;
+
+use crate::opset::Opset;
+use crate::value::{Membership, Raw};
+
+
+/// Fuzzy set over real numbers, given by memberships at evenly spaced
+/// points of an interval.
+///
+/// Memberships between the points are interpolated linearly, ones
+/// outside of the interval are `0`. Usually made by sampling
+/// a `ContinuousFuzzySet`:
+///
+/// ```
+/// # use fuzzy_systems::{ContinuousFuzzySet, Hamacher1};
+/// let warm = ContinuousFuzzySet::<Hamacher1>::triangular(10.0, 20.0, 30.0);
+/// let sampled = warm.sample(5);
+///
+/// let points: Vec<_> = sampled.points().collect();
+/// assert_eq!(points, [10.0, 15.0, 20.0, 25.0, 30.0]);
+/// assert_eq!(sampled.membership(17.5).as_raw(), 0.75);
+/// assert_eq!(sampled.support(), [10.0..=30.0]);
+/// assert_eq!(sampled.alpha_cut(0.5), [15.0..=25.0]);
+/// ```
+pub struct SampledFuzzySet<S: Opset> {
+    range: (Raw, Raw),
+    vals: Vec<Membership<S>>
+}
+
+impl<S: Opset> SampledFuzzySet<S> {
+    /// Creates set from memberships at evenly spaced points of the
+    /// range, its bounds included. Panics if there are less than 2.
+    pub fn new(range: RangeInclusive<Raw>, vals: Vec<Membership<S>>) -> Self {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi);
+        assert!(vals.len() >= 2);
+        Self {
+            range: (lo, hi),
+            vals
+        }
+    }
+
+    /// Creates set from memberships given by `f` at `points` evenly
+    /// spaced points of the range, its bounds included.
+    pub fn from_fn<F>(range: RangeInclusive<Raw>, points: usize, mut f: F)
+        -> Self
+    where
+        F: FnMut(Raw) -> Membership<S>
+    {
+        let (lo, hi) = range.into_inner();
+        assert!(points >= 2);
+        let vals = (0..points)
+            .map(|i| f(Self::point_of(lo, hi, points, i)))
+            .collect();
+        Self::new(lo..=hi, vals)
+    }
+
+    /// Bounds of the sampled interval.
+    pub fn range(&self) -> (Raw, Raw) {
+        self.range
+    }
+
+    /// Number of points.
+    pub fn len(&self) -> usize {
+        self.vals.len()
+    }
+
+    /// Always `false`, there are at least 2 points.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Sampled points.
+    pub fn points(&self) -> impl Iterator<Item = Raw> + '_ {
+        (0..self.vals.len()).map(move |i| self.point(i))
+    }
+
+    /// Memberships at the sampled points.
+    pub fn memberships(&self) -> &[Membership<S>] {
+        &self.vals
+    }
+
+    /// Sampled points with their memberships.
+    pub fn iter(&self) -> impl Iterator<Item = (Raw, Membership<S>)> + '_ {
+        self.points().zip(self.vals.iter().copied())
+    }
+
+    /// Membership of the point, interpolated between the samples.
+    pub fn membership(&self, x: Raw) -> Membership<S> {
+        let (lo, hi) = self.range;
+        if !(x >= lo && x <= hi) {
+            return Membership::new(0.0);
+        }
+        if lo == hi {
+            return self.vals[0];
+        }
+
+        let t = (x - lo) / (hi - lo) * (self.vals.len() - 1) as Raw;
+        let i = (t as usize).min(self.vals.len() - 2);
+        let ft = t - i as Raw;
+        let (a, b) = (self.vals[i].as_raw(), self.vals[i + 1].as_raw());
+        Membership::with_fit(a * (1.0 - ft) + b * ft)
+    }
+
+    /// Largest sampled membership.
+    pub fn height(&self) -> Membership<S> {
+        self.vals.iter()
+            .copied()
+            .fold(Membership::new(0.0), |max, val| {
+                if val > max { val } else { max }
+            })
+    }
+
+    /// Intervals of nonzero membership.
+    ///
+    /// Bounds are estimated by interpolation between the samples,
+    /// as for all cuts.
+    pub fn support(&self) -> Vec<RangeInclusive<Raw>> {
+        self.cut(0.0, true)
+    }
+
+    /// Intervals of full membership.
+    pub fn core(&self) -> Vec<RangeInclusive<Raw>> {
+        self.cut(1.0, false)
+    }
+
+    /// Intervals of membership at least `alpha` (α-cut).
+    pub fn alpha_cut(&self, alpha: Raw) -> Vec<RangeInclusive<Raw>> {
+        self.cut(alpha, false)
+    }
+
+    /// Intervals of membership above `alpha` (strong α-cut).
+    pub fn strong_alpha_cut(&self, alpha: Raw) -> Vec<RangeInclusive<Raw>> {
+        self.cut(alpha, true)
+    }
+
+    /// α-cuts at `levels` evenly spaced levels `1 / levels`, ..., `1`.
+    ///
+    /// The set rebuilt from them (see `LevelSets::to_set`) has
+    /// memberships rounded down to the levels.
+    pub fn level_sets(&self, levels: usize)
+        -> LevelSets<S, Vec<RangeInclusive<Raw>>> {
+
+        assert!(levels > 0);
+        let levels = (1..=levels)
+            .map(|k| {
+                let alpha = Membership::new(k as Raw / levels as Raw);
+                (alpha, self.alpha_cut(alpha.as_raw()))
+            })
+            .collect();
+        LevelSets::new(levels)
+    }
+
+    /// Intervals of membership above `alpha`, or at least `alpha`
+    /// if not `strict`.
+    fn cut(&self, alpha: Raw, strict: bool)
+        -> Vec<RangeInclusive<Raw>> {
+
+        let inside = |i: usize| {
+            let val = self.vals[i].as_raw();
+            if strict { val > alpha } else { val >= alpha }
+        };
+        // bound between point `out` outside and `inner` inside
+        let bound = |out: usize, inner: usize| {
+            let (a, b) = (self.vals[out].as_raw(), self.vals[inner].as_raw());
+            let t = ((alpha - a) / (b - a)).clamp(0.0, 1.0);
+            self.point(out) * (1.0 - t) + self.point(inner) * t
+        };
+
+        let last = self.vals.len() - 1;
+        let mut cut = Vec::new();
+        let mut i = 0;
+        while i <= last {
+            if !inside(i) {
+                i += 1;
+                continue;
+            }
+            let start = if i == 0 { self.point(0) } else { bound(i - 1, i) };
+            while i < last && inside(i + 1) {
+                i += 1;
+            }
+            let end = if i < last { bound(i + 1, i) } else { self.point(i) };
+            cut.push(start..=end);
+            i += 1;
+        }
+        cut
+    }
+
+    #[inline]
+    fn point(&self, i: usize) -> Raw {
+        Self::point_of(self.range.0, self.range.1, self.vals.len(), i)
+    }
+
+    #[inline]
+    fn point_of(lo: Raw, hi: Raw, points: usize, i: usize) -> Raw {
+        if i == points - 1 {
+            hi
+        } else {
+            lo + (hi - lo) * i as Raw / (points - 1) as Raw
+        }
+    }
+}
+
+impl<S: Opset> Clone for SampledFuzzySet<S> {
+    fn clone(&self) -> Self {
+        Self {
+            range: self.range,
+            vals: self.vals.clone()
+        }
+    }
+}
+
+impl<S: Opset> PartialEq for SampledFuzzySet<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.range == other.range && self.vals == other.vals
+    }
+}
+
+impl<S: Opset> Debug for SampledFuzzySet<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let (lo, hi) = self.range;
+        write!(f, "SampledFuzzySet({:?}..={:?}, {:?})", lo, hi, self.vals)
+    }
+}
@@ -0,0 +1,224 @@
+use super::*;
+
+use crate::opset::{Hamacher1, Opset, YagerInf};
+use crate::value::Membership;
+
+
+#[test]
+fn discrete() {
+    let set = |vals: &[(char, f64)]| -> DiscreteFuzzySet<char, YagerInf> {
+        vals.iter().map(|&(elem, raw)| (elem, Membership::new(raw))).collect()
+    };
+    let a = set(&[('a', 0.2), ('b', 1.0), ('c', 0.0)]);
+    let b = set(&[('b', 0.4), ('d', 0.7)]);
+
+    assert_eq!(a.len(), 3);
+    assert!(a.contains(&'c') && !a.contains(&'d'));
+    assert_eq!(a.support().collect::<String>(), "ab");
+    assert_eq!(a.core().collect::<String>(), "b");
+    assert!(a.is_normal() && !b.is_normal());
+    assert_eq!(b.height().as_raw(), 0.7);
+
+    assert_eq!(&a | &b, set(&[('a', 0.2), ('b', 1.0), ('c', 0.0), ('d', 0.7)]));
+    assert_eq!(&a & &b, set(&[('a', 0.0), ('b', 0.4), ('c', 0.0), ('d', 0.0)]));
+    assert_eq!(!&b, set(&[('b', 0.6), ('d', 1.0 - 0.7)]));
+
+    // laws of the opset carry over
+    let c = set(&[('a', 0.5), ('c', 0.9), ('e', 0.1)]);
+    assert_eq!(&a & &(&b | &c), &(&a & &b) | &(&a & &c));
+    // complements are within universes, so alike for De Morgan's laws
+    let d = set(&[('a', 0.5), ('b', 0.3), ('c', 0.9)]);
+    assert_eq!(!&(&a | &d), &!&a & &!&d);
+
+    let normal = b.normalize().unwrap();
+    assert!(normal.is_normal());
+    assert_eq!(normal.core().collect::<String>(), "d");
+    assert!((normal.membership(&'b').as_raw() - 0.4 / 0.7).abs() < 1e-12);
+    assert_eq!(set(&[('a', 0.0)]).normalize(), None);
+    let empty = DiscreteFuzzySet::<char, YagerInf>::new();
+    assert_eq!(empty.height().as_raw(), 0.0);
+
+    // opset-specific operations
+    let x: DiscreteFuzzySet<_, Hamacher1> =
+        vec![(1, Hamacher1::member(0.5)), (2, Hamacher1::member(0.2))]
+            .into_iter()
+            .collect();
+    let y = x.intersection(&x);
+    assert_eq!(y.membership(&1).as_raw(), 0.25);
+    assert_eq!(y.iter().len(), 2);
+    assert_eq!(x.into_iter().map(|(elem, _)| elem).sum::<i32>(), 3);
+}
+
+#[test]
+fn continuous() {
+    use crate::expr::{Hedge, Weight};
+
+    type Set = ContinuousFuzzySet<Hamacher1>;
+    let slow = Set::trapezoidal(0.0, 0.0, 20.0, 40.0);
+    let fast = Set::trapezoidal(60.0, 80.0, 120.0, 120.0);
+    let cruise = Set::gaussian(50.0, 5.0);
+    assert_eq!(cruise.universe(), (30.0, 70.0));
+    assert_eq!(cruise.membership(50.0).as_raw(), 1.0);
+
+    // shoulders, also beyond the universe
+    assert_eq!(slow.membership(0.0).as_raw(), 1.0);
+    assert_eq!(slow.membership(-0.001).as_raw(), 1.0);
+    assert_eq!(slow.membership(40.001).as_raw(), 0.0);
+    assert_eq!(fast.membership(120.0).as_raw(), 1.0);
+    assert_eq!(fast.membership(120.001).as_raw(), 1.0);
+    assert_eq!(fast.membership(59.999).as_raw(), 0.0);
+    assert_eq!(fast.membership(130.0).as_raw(), 1.0);
+    assert_eq!(slow.membership(f64::NAN).as_raw(), 0.0);
+    assert_eq!(fast.membership(f64::NAN).as_raw(), 0.0);
+    assert_eq!(Set::triangular(1.0, 2.0, 3.0).membership(f64::NAN).as_raw(), 0.0);
+
+    let extremes = slow.clone() | fast.clone();
+    assert_eq!(extremes.universe(), (0.0, 120.0));
+    assert_eq!(extremes.core(121), [0.0..=20.0, 80.0..=120.0]);
+    assert_eq!(extremes.alpha_cut(0.5, 121), [0.0..=30.0, 70.0..=120.0]);
+    assert_eq!(extremes.support(121), [0.0..=40.0, 60.0..=120.0]);
+
+    // estimates are as good as the sampling
+    let cut = cruise.alpha_cut(0.5, 1001);
+    let exact = 5.0 * (2.0 * 2.0f64.ln()).sqrt();
+    assert_eq!(cut.len(), 1);
+    assert!((cut[0].start() - (50.0 - exact)).abs() < 0.04);
+    assert!((cut[0].end() - (50.0 + exact)).abs() < 0.04);
+
+    let both = slow.clone() & !cruise.clone();
+    for &x in &[10.0, 35.0, 45.0] {
+        let expected = slow.membership(x) & !cruise.membership(x);
+        assert_eq!(both.membership(x), expected);
+    }
+
+    let hedged = cruise.clone().hedge(Hedge::Very);
+    let weighted = cruise.clone().modify(Weight::Or(Membership::new(0.5)));
+    assert_eq!(hedged.membership(45.0), cruise.membership(45.0).concentrate());
+    assert_eq!(weighted.core(101), []);
+    assert!(slow.clone().with_universe(50.0..=60.0).support(11).is_empty());
+
+    let sampled = slow.sample(5);
+    assert_eq!(sampled.len(), 5);
+    assert_eq!(sampled.height().as_raw(), 1.0);
+    assert_eq!(sampled.membership(-1.0).as_raw(), 0.0);
+    assert_eq!(sampled.membership(25.0).as_raw(), 0.75);
+    assert_eq!(sampled.iter().last().map(|(x, val)| (x, val.as_raw())),
+        Some((40.0, 0.0)));
+}
+
+#[test]
+fn alpha_cuts() {
+    let set: DiscreteFuzzySet<u8, YagerInf> = (0..=10)
+        .map(|i| (i, Membership::new((i % 5) as f64 / 4.0)))
+        .collect();
+    let cut = |cut: Vec<&u8>| cut.into_iter().copied().collect::<Vec<_>>();
+    assert_eq!(cut(set.alpha_cut(0.75).collect()), [3, 4, 8, 9]);
+    assert_eq!(cut(set.strong_alpha_cut(0.75).collect()), [4, 9]);
+    assert_eq!(cut(set.strong_alpha_cut(1.0).collect()), []);
+
+    // exact, universe kept by the 0-cut
+    let levels = set.level_sets();
+    assert_eq!(levels.len(), 5);
+    assert_eq!(levels.levels()[0].1.len(), 11);
+    assert_eq!(levels.to_set(), set);
+    assert_eq!(levels.membership(&7).as_raw(), 0.5);
+    assert_eq!(levels.membership(&11).as_raw(), 0.0);
+
+    // nested, approximated from below
+    let bumps = ContinuousFuzzySet::<YagerInf>::triangular(0.0, 2.0, 4.0)
+        | ContinuousFuzzySet::triangular(4.0, 5.0, 6.0);
+    let sampled = bumps.sample(61);
+    assert_eq!(sampled.strong_alpha_cut(0.0), [0.0..=4.0, 4.0..=6.0]);
+    assert_eq!(sampled.alpha_cut(0.0), [0.0..=6.0]);
+    assert_eq!(bumps.strong_alpha_cut(0.5, 61), [1.0..=3.0, 4.5..=5.5]);
+
+    let levels = bumps.level_sets(10, 61);
+    for pair in levels.levels().windows(2) {
+        assert!(pair[0].0 < pair[1].0);
+        for range in &pair[1].1 {
+            assert!(pair[0].1.iter().any(|outer| {
+                outer.start() <= range.start() && range.end() <= outer.end()
+            }));
+        }
+    }
+    let rebuilt = levels.to_set();
+    let (lo, hi) = rebuilt.universe();
+    assert!((lo - 0.2).abs() < 1e-9 && (hi - 5.9).abs() < 1e-9);
+    for i in 0..=600 {
+        let x = i as f64 / 100.0;
+        let (exact, approx) = (bumps.membership(x), rebuilt.membership(x));
+        assert!(approx.as_raw() <= exact.as_raw() + 1e-9);
+        assert!(exact.as_raw() - approx.as_raw() < 0.1 + 1e-9);
+    }
+}
+
+#[test]
+fn numbers() {
+    type Number = FuzzyNumber<YagerInf>;
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    let close_range = |range: std::ops::RangeInclusive<f64>, lo, hi| {
+        close(*range.start(), lo) && close(*range.end(), hi)
+    };
+
+    let a = Number::triangular(1.0, 2.0, 3.0);
+    let b = Number::trapezoidal(4.0, 5.0, 6.0, 8.0);
+    assert_eq!(a.membership(1.5).as_raw(), 0.5);
+    assert_eq!(a.membership(3.5).as_raw(), 0.0);
+    assert_eq!(b.membership(5.5).as_raw(), 1.0);
+    assert!(close(b.membership(7.5).as_raw(), 0.25));
+    assert!(close_range(b.alpha_cut(0.5), 4.5, 7.0));
+    assert!(close(a.defuzzify(), 2.0));
+
+    // interval arithmetic on each cut
+    let sum = a + b;
+    assert!(close_range(sum.support(), 5.0, 11.0));
+    assert!(close_range(sum.core(), 7.0, 8.0));
+    assert!(close_range((b - a).support(), 1.0, 7.0));
+    assert!(close_range((a * b).core(), 10.0, 12.0));
+    assert!(close_range((a * b).alpha_cut(0.5), 6.75, 17.5));
+    assert!(close_range((b / a).support(), 4.0 / 3.0, 8.0));
+    assert!(close_range((-a * 2.0 + 1.0).support(), -5.0, -1.0));
+    assert!(close_range((b / 2.0 - 1.0).core(), 1.5, 2.0));
+    // not crisp 0, operands are independent
+    assert!(close_range((a - a).support(), -2.0, 2.0));
+    assert!(b.checked_div(a - a).is_none());
+    assert_eq!(Number::crisp(3.0) + 1.0, Number::crisp(4.0));
+
+    // LR numbers
+    let flat = Number::lr(
+        0.0, 0.0, 1.0, 2.0, LrShape::Power(2.0), LrShape::Linear
+    );
+    assert!(close(flat.membership(-0.5).as_raw(), 0.75));
+    assert!(close(flat.membership(1.0).as_raw(), 0.5));
+
+    // comparisons
+    assert_eq!(a.possibly_le(&b).as_raw(), 1.0);
+    assert_eq!(b.possibly_le(&a).as_raw(), 0.0);
+    assert_eq!(a.necessarily_le(&b).as_raw(), 1.0);
+    let c = Number::triangular(1.5, 2.5, 3.5);
+    assert_eq!(a.possibly_le(&c).as_raw(), 1.0);
+    assert_eq!(a.possibly_ge(&c).as_raw(), 0.75);
+    assert_eq!(a.necessarily_le(&c).as_raw(), 0.25);
+    assert_eq!(c.necessarily_ge(&a).as_raw(), 0.25);
+
+    // conversions
+    let set = a.to_set();
+    assert_eq!(set.universe(), (1.0, 3.0));
+    assert_eq!(set.membership(2.5).as_raw(), 0.5);
+    let levels = sum.level_sets();
+    assert_eq!(levels.len(), 21);
+    assert_eq!(levels.membership(&7.5).as_raw(), 1.0);
+
+    // extension principle: min agrees with the cuts, product is wider
+    let by_min = a.extend(&b, |x, y| x + y, 101);
+    assert!(close(by_min.range().0, 5.0) && close(by_min.range().1, 11.0));
+    for x in [6.0, 7.5, 9.0, 10.0] {
+        let diff = by_min.membership(x).as_raw() - sum.membership(x).as_raw();
+        assert!(diff.abs() < 0.05);
+    }
+    let a = FuzzyNumber::<Hamacher1>::from_cuts(*a.cuts());
+    let b = FuzzyNumber::<Hamacher1>::from_cuts(*b.cuts());
+    let by_and = a.extend(&b, |x, y| x + y, 101);
+    assert!(by_and.membership(9.0).as_raw() < by_min.membership(9.0).as_raw());
+    assert_eq!(by_and.height().as_raw(), 1.0);
+}
@@ -1,5 +1,7 @@
 mod traits;
 mod membership;
+mod lanes;
 
 pub use self::traits::*;
 pub use self::membership::*;
+pub use self::lanes::*;
@@ -0,0 +1,145 @@
+use std::array;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::simd::f64x8;
+use std::simd::num::SimdFloat;
+use std::simd::StdFloat;
+
+use super::{Membership, Raw};
+
+use crate::opset::Opset;
+
+
+/// Number of lanes of the lane-wise opset operations (`Opset::and_x8`, ...).
+pub const LANES: usize = 8;
+
+/// Membership values of all lanes.
+pub type MembershipX8<S> = [Membership<S>; LANES];
+
+
+/// Raw membership values of 8 lanes, operated on at once.
+///
+/// Formulas of `simd` opsets of `fuzzy_opset!` are evaluated on lanes as
+/// well as on single values, so it supports arithmetic (also with `Raw`
+/// constants on either side), `min`, `max`, `abs`, `sqrt`, `mul_add` and,
+/// lane by lane, `powf`, `powi`, `exp` and `ln`.
+#[doc(hidden)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Lanes(f64x8);
+
+impl Lanes {
+    #[inline]
+    pub fn from_members<S: Opset>(vals: MembershipX8<S>) -> Self {
+        Lanes(f64x8::from_array(vals.map(Membership::as_raw)))
+    }
+
+    /// Members of the lanes.
+    /// Does not check anything, as `Membership::unchecked_new`.
+    #[inline]
+    pub fn to_members<S: Opset>(self) -> MembershipX8<S> {
+        self.0.to_array().map(Membership::unchecked_new)
+    }
+
+    #[inline]
+    pub fn min(self, other: impl Into<Lanes>) -> Self {
+        Lanes(self.0.simd_min(other.into().0))
+    }
+
+    #[inline]
+    pub fn max(self, other: impl Into<Lanes>) -> Self {
+        Lanes(self.0.simd_max(other.into().0))
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self {
+        Lanes(self.0.abs())
+    }
+
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        Lanes(self.0.sqrt())
+    }
+
+    #[inline]
+    pub fn mul_add(self, a: impl Into<Lanes>, b: impl Into<Lanes>) -> Self {
+        Lanes(self.0.mul_add(a.into().0, b.into().0))
+    }
+
+    #[inline]
+    pub fn powf(self, exp: impl Into<Lanes>) -> Self {
+        let vals = self.0.to_array();
+        let exp = exp.into().0.to_array();
+        Lanes(f64x8::from_array(array::from_fn(|i| vals[i].powf(exp[i]))))
+    }
+
+    #[inline]
+    pub fn powi(self, exp: i32) -> Self {
+        self.each(|val| val.powi(exp))
+    }
+
+    #[inline]
+    pub fn exp(self) -> Self {
+        self.each(Raw::exp)
+    }
+
+    #[inline]
+    pub fn ln(self) -> Self {
+        self.each(Raw::ln)
+    }
+
+    #[inline]
+    fn each(self, f: impl FnMut(Raw) -> Raw) -> Self {
+        Lanes(f64x8::from_array(self.0.to_array().map(f)))
+    }
+}
+
+impl From<Raw> for Lanes {
+    #[inline]
+    fn from(val: Raw) -> Self {
+        Lanes(f64x8::splat(val))
+    }
+}
+
+impl Neg for Lanes {
+    type Output = Lanes;
+
+    #[inline]
+    fn neg(self) -> Lanes {
+        Lanes(-self.0)
+    }
+}
+
+/// Implement binary operator for lanes, with raw values on either side.
+macro_rules! impl_lanes_op {
+    ($( $op:ident $fun:ident ),*) => {$(
+        impl $op for Lanes {
+            type Output = Lanes;
+
+            #[inline]
+            fn $fun(self, rhs: Lanes) -> Lanes {
+                Lanes($op::$fun(self.0, rhs.0))
+            }
+        }
+
+        impl $op<Raw> for Lanes {
+            type Output = Lanes;
+
+            #[inline]
+            fn $fun(self, rhs: Raw) -> Lanes {
+                Lanes($op::$fun(self.0, f64x8::splat(rhs)))
+            }
+        }
+
+        impl $op<Lanes> for Raw {
+            type Output = Lanes;
+
+            #[inline]
+            fn $fun(self, rhs: Lanes) -> Lanes {
+                Lanes($op::$fun(f64x8::splat(self), rhs.0))
+            }
+        }
+    )*};
+}
+
+impl_lanes_op! {
+    Add add, Sub sub, Mul mul, Div div
+}
@@ -33,7 +33,9 @@ impl<S: Opset> From<Raw> for Membership<S> {
 
 impl<S: Opset> Membership<S> {
     /// Checks whether the precondition of the construction is fulfilled.
-    fn precondition(val: Raw) -> bool {
+    // `RangeInclusive::contains` is not `const`.
+    #[allow(clippy::manual_range_contains)]
+    const fn precondition(val: Raw) -> bool {
         val >= Self::MIN_VALUE && val <= Self::MAX_VALUE
     }
 
@@ -44,8 +46,15 @@ impl<S: Opset> Membership<S> {
     const MAX_VALUE: Raw = 1.0;
 
     /// Creates a membership value based on raw numerical value.
-    /// Panics if the value is not between 0.0 and 1.0 inclusive.
-    pub fn new(val: Raw) -> Self {
+    /// Panics if the value is not between 0.0 and 1.0 inclusive,
+    /// which fails compilation when evaluated in a constant:
+    ///
+    /// ```compile_fail
+    /// # use fuzzy_systems::{Hamacher1, Membership};
+    /// const HIGH: Membership<Hamacher1> = Membership::new(1.5);
+    /// # let _ = HIGH;
+    /// ```
+    pub const fn new(val: Raw) -> Self {
         assert!(Self::precondition(val));
         Self::unchecked_new(val)
     }
@@ -53,12 +62,12 @@ impl<S: Opset> Membership<S> {
     /// Creates a membership value based on raw numerical value.
     /// Does not check anything. Use only for cases where precondition
     /// is proved to be true.
-    pub fn unchecked_new(val: Raw) -> Self {
+    pub const fn unchecked_new(val: Raw) -> Self {
         Membership(val, PhantomData)
     }
 
     /// Creates a membership value based on raw numerical value.
-    pub fn try_new(val: Raw) -> Option<Self> {
+    pub const fn try_new(val: Raw) -> Option<Self> {
         if Self::precondition(val) {
             Some(Self::unchecked_new(val))
         }
@@ -69,16 +78,45 @@ impl<S: Opset> Membership<S> {
 
     /// Creates a membership value based on raw numerical value.
     /// Panics if the value is not between 0.0 and 1.0 inclusive.
-    pub fn with_fit(val: Raw) -> Self {
+    pub const fn with_fit(val: Raw) -> Self {
         assert!(!val.is_nan());
-        let val = val.max(Self::MIN_VALUE).min(Self::MAX_VALUE);
+        let val = val.clamp(Self::MIN_VALUE, Self::MAX_VALUE);
         Self::unchecked_new(val)
     }
 
     /// Raw numerical value of the membership value.
-    pub fn as_raw(self) -> Raw {
+    pub const fn as_raw(self) -> Raw {
         self.0
     }
+
+    /// Concentration, `a^2` ("very").
+    pub fn concentrate(self) -> Self {
+        Self::unchecked_new(self.0 * self.0)
+    }
+
+    /// Dilation, `a^0.5` ("somewhat").
+    pub fn dilate(self) -> Self {
+        Self::unchecked_new(self.0.sqrt())
+    }
+
+    /// Contrast intensification ("indeed"): `2a^2` up to `0.5`,
+    /// `1 - 2(1 - a)^2` above.
+    pub fn intensify(self) -> Self {
+        if self.0 <= 0.5 {
+            Self::unchecked_new(2.0 * self.0 * self.0)
+        }
+        else {
+            let rest = 1.0 - self.0;
+            Self::unchecked_new(1.0 - 2.0 * rest * rest)
+        }
+    }
+
+    /// Powered hedge, `a^exp`.
+    /// Panics if the exponent is negative or NaN.
+    pub fn pow(self, exp: Raw) -> Self {
+        assert!(exp >= 0.0);
+        Self::unchecked_new(self.0.powf(exp))
+    }
 }
 
 impl<S: Opset> Deref for Membership<S> {
@@ -109,6 +147,19 @@ fn membership_ops() {
     assert_eq!((x | y).as_raw(), 0.5);
 }
 
+#[test]
+fn membership_hedges() {
+    use crate::opset::YagerInf;
+
+    let x = Membership::<YagerInf>::new(0.25);
+    assert_eq!(x.concentrate().as_raw(), 0.0625);
+    assert_eq!(x.dilate().as_raw(), 0.5);
+    assert_eq!(x.intensify().as_raw(), 0.125);
+    assert_eq!(Membership::<YagerInf>::new(0.75).intensify().as_raw(), 0.875);
+    assert_eq!(x.pow(3.0).as_raw(), 0.015625);
+    assert_eq!(x.pow(0.0).as_raw(), 1.0);
+}
+
 
 impl<S: Opset> Debug for Membership<S> {
     fn fmt(&self, fmt: &mut Formatter) -> fmtResult {
@@ -120,6 +171,6 @@ impl<S: Opset> Debug for Membership<S> {
 
 impl<S: Opset> Display for Membership<S> {
     fn fmt(&self, fmt: &mut Formatter) -> fmtResult {
-        write!(fmt, "{}", self.0)
+        Display::fmt(&self.0, fmt)
     }
 }